@@ -0,0 +1,106 @@
+//! Text helpers for the predefined template functions
+
+/// words on an identifier or text
+/// separated by non alphanumeric chars and case changes
+/// ie: `HTTPServer_name` -> `HTTP`, `Server`, `name`
+fn words(txt: &str) -> Vec<String> {
+    let chars = txt.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut current = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1);
+        let starts_word = ch.is_uppercase()
+            && match prev {
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        if starts_word && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// ie: `http_server_name` -> `httpServerName`
+pub(crate) fn camel_case(txt: &str) -> String {
+    words(txt)
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            let lower = w.to_lowercase();
+            if i == 0 {
+                lower
+            } else {
+                let mut chars = lower.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        })
+        .collect()
+}
+
+/// ie: `HTTPServerName` -> `http_server_name`
+pub(crate) fn snake_case(txt: &str) -> String {
+    words(txt)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// content for a rust string literal (without quotes)
+pub(crate) fn escape_rust(txt: &str) -> String {
+    txt.escape_debug().to_string()
+}
+
+/// content for a json string (without quotes)
+pub(crate) fn escape_json(txt: &str) -> String {
+    txt.chars().fold(String::new(), |mut acc, ch| {
+        match ch {
+            '"' => acc.push_str("\\\""),
+            '\\' => acc.push_str("\\\\"),
+            '\n' => acc.push_str("\\n"),
+            '\r' => acc.push_str("\\r"),
+            '\t' => acc.push_str("\\t"),
+            '\u{08}' => acc.push_str("\\b"),
+            '\u{0c}' => acc.push_str("\\f"),
+            ch if ch.is_control() => acc.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => acc.push(ch),
+        }
+        acc
+    })
+}
+
+/// text for xml or html content and attributes
+pub(crate) fn escape_xml(txt: &str) -> String {
+    txt.chars().fold(String::new(), |mut acc, ch| {
+        match ch {
+            '&' => acc.push_str("&amp;"),
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '"' => acc.push_str("&quot;"),
+            '\'' => acc.push_str("&apos;"),
+            ch => acc.push(ch),
+        }
+        acc
+    })
+}
+
+/// single quoted shell word (with quotes)
+pub(crate) fn escape_shell(txt: &str) -> String {
+    format!("'{}'", txt.replace('\'', "'\\''"))
+}
@@ -2,33 +2,90 @@
 //! And some functions to work with AST
 //!
 
+pub(crate) mod functions;
 pub(crate) mod replace;
+pub mod typed;
 
 // -------------------------------------------------------------------------------------
 //  T Y P E S
 
+use crate::parser::Possition;
+
+/// Start and end possitions of a node on the parsed text
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub(crate) start: Possition,
+    pub(crate) end: Possition,
+}
+
 /// template to transform and
 /// nodes to be used on transform
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Transf2 {
     /// template info to replace
     pub(crate) template: crate::parser::expression::ReplTemplate,
     /// nodes on witch will be applied the transformation
     pub(crate) nodes: Vec<Node>,
+    /// parsed text for this transformation
+    pub(crate) span: Span,
 }
 
 /// Information of a node
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Node {
     /// The node is terminal (atom) with a name
-    Val(String),
+    Val((String, Span)),
     /// The node is not terminal (rule)
     /// with a name and a vec of nodes
-    Rule((String, Vec<Node>)),
+    Rule((String, Vec<Node>, Span)),
     /// Named nodes
-    Named((String, Vec<Node>)),
+    Named((String, Vec<Node>, Span)),
     /// Named nodes
     Transf2(Transf2),
     /// Reached end of file
-    Eof,
+    Eof(Span),
+}
+
+// -------------------------------------------------------------------------------------
+//  A P I
+
+impl Span {
+    pub(crate) fn new(start: Possition, end: Possition) -> Self {
+        Span { start, end }
+    }
+
+    /// possition where the node starts
+    pub fn start(&self) -> &Possition {
+        &self.start
+    }
+
+    /// possition after the last char of the node
+    pub fn end(&self) -> &Possition {
+        &self.end
+    }
+
+    /// slice of parsed text for this span
+    /// `text` has to be the same text given to parse
+    pub fn source<'s>(&self, text: &'s str) -> &'s str {
+        &text[self.start.n..self.end.n]
+    }
+}
+
+impl Node {
+    /// start and end possitions of the node on the parsed text
+    pub fn span(&self) -> &Span {
+        match self {
+            Node::Val((_, span))
+            | Node::Rule((_, _, span))
+            | Node::Named((_, _, span))
+            | Node::Transf2(Transf2 { span, .. })
+            | Node::Eof(span) => span,
+        }
+    }
+
+    /// slice of parsed text matched by the node
+    /// `text` has to be the same text given to parse
+    pub fn source<'s>(&self, text: &'s str) -> &'s str {
+        self.span().source(text)
+    }
 }
@@ -1,42 +1,266 @@
 use crate::ast::Node;
-use idata::IString;
+use crate::parser::expression::{NameIndex, ReplCond, ReplTemplate};
+use crate::parser::Possition;
+use crate::ReplaceFn;
+use std::collections::HashMap;
 
+/// Nodes on a template, replaced when the template uses them
+///
+/// A node is written straight to the output where it's used. Its replaced
+/// text is kept only when the value is needed (conditions and functions),
+/// and then it's reused
 #[derive(Debug)]
-pub(crate) struct ReplacedNodes {
-    by_pos: Vec<Replaced>,
-    by_name: im::HashMap<String, Replaced>,
+pub(crate) struct ReplacedNodes<'n> {
+    by_pos: Vec<&'n Node>,
+    /// positions of all the occurrences of each name
+    by_name: HashMap<&'n str, Vec<usize>>,
+    /// replaced text of the nodes, when kept
+    values: Vec<Option<String>>,
 }
 
-impl ReplacedNodes {
-    fn new() -> Self {
+impl<'n> ReplacedNodes<'n> {
+    /// nodes not named (values, other templates...) are not visible on template
+    fn new(nodes: &'n [Node]) -> Self {
+        let mut by_pos = Vec::<&Node>::new();
+        let mut by_name = HashMap::<&str, Vec<usize>>::new();
+        for node in nodes {
+            match node {
+                Node::Named((name, _, _)) | Node::Rule((name, _, _)) => {
+                    by_name.entry(name).or_default().push(by_pos.len());
+                    by_pos.push(node);
+                }
+                Node::Val(_) | Node::Transf2(_) | Node::Eof(_) => (),
+            }
+        }
         ReplacedNodes {
-            by_pos: Vec::<Replaced>::new(),
-            by_name: im::HashMap::<String, Replaced>::new(),
+            values: vec![None; by_pos.len()],
+            by_pos,
+            by_name,
         }
     }
 
-    fn process_node(mut self, node: &Node) -> Result<Self, String> {
-        let node_replaced = replace(node, None)?;
-        match node {
-            Node::Named((name, _nodes)) => {
-                self.by_pos.push(node_replaced.clone());
-                self.by_name.insert(name.clone(), node_replaced);
-            }
-            Node::Rule((name, _nodes)) => {
-                self.by_pos.push(node_replaced.clone());
-                self.by_name.insert(name.clone(), node_replaced);
+    fn all_by_name<'s>(&'s self, name: &str) -> impl Iterator<Item = usize> + 's {
+        self.by_name.get(name).into_iter().flatten().copied()
+    }
+
+    /// last occurrence
+    fn get_by_name(&self, name: &str) -> Option<usize> {
+        self.all_by_name(name).last()
+    }
+
+    fn get_by_name_index(&self, name: &str, index: &NameIndex) -> Option<usize> {
+        match index {
+            NameIndex::First => self.all_by_name(name).next(),
+            NameIndex::Last => self.all_by_name(name).last(),
+            NameIndex::Nth(n) => n.checked_sub(1).and_then(|n| self.all_by_name(name).nth(n)),
+        }
+    }
+
+    fn count_by_name(&self, name: &str) -> usize {
+        self.by_name
+            .get(name)
+            .map_or(0, |occurrences| occurrences.len())
+    }
+
+    /// 1 based
+    fn get_by_pos(&self, pos: usize) -> Option<usize> {
+        pos.checked_sub(1).filter(|p| *p < self.by_pos.len())
+    }
+
+    /// write the node on `p`, replacing it on `out` if it's not kept
+    fn write(
+        &self,
+        p: usize,
+        replacer: &mut Replacer,
+        rule: &str,
+        out: &mut Out,
+    ) -> Result<(), String> {
+        match &self.values[p] {
+            Some(value) => out.push(value),
+            None => rec_replace(self.by_pos[p], replacer, rule, out),
+        }
+    }
+
+    /// replaced text of the node on `p`, kept to be reused
+    fn value(&mut self, p: usize, replacer: &mut Replacer, rule: &str) -> Result<&str, String> {
+        if self.values[p].is_none() {
+            let mut value = String::new();
+            rec_replace(self.by_pos[p], replacer, rule, &mut Out::new(&mut value))?;
+            self.values[p] = Some(value);
+        }
+        Ok(self.values[p].as_deref().unwrap_or(""))
+    }
+
+    /// keep the replaced text of all the nodes
+    fn keep_values(&mut self, replacer: &mut Replacer, rule: &str) -> Result<(), String> {
+        (0..self.by_pos.len()).try_for_each(|p| self.value(p, replacer, rule).map(|_| ()))
+    }
+
+    fn kept_value(&self, p: usize) -> Option<&str> {
+        self.values[p].as_deref()
+    }
+}
+
+/// Where the replaced text is written
+pub(crate) trait Output {
+    fn write(&mut self, txt: &str) -> Result<(), String>;
+}
+
+impl Output for String {
+    fn write(&mut self, txt: &str) -> Result<(), String> {
+        self.push_str(txt);
+        Ok(())
+    }
+}
+
+/// Write replaced text as it's produced
+pub(crate) struct WriteOutput<'a>(pub(crate) &'a mut dyn std::io::Write);
+
+impl<'a> Output for WriteOutput<'a> {
+    fn write(&mut self, txt: &str) -> Result<(), String> {
+        self.0
+            .write_all(txt.as_bytes())
+            .map_err(|e| format!("writing output: {}", e))
+    }
+}
+
+const INDENT: &str = "    ";
+
+/// Output keeping the indentation level  `$(:indent)`  `$(:dedent)`
+///
+/// Lines are prefixed when the first char is written, so empty lines
+/// are not indented.
+/// The nodes on a template are written with the template indentation.
+/// The kept text of a node is indented relative to zero, and it's
+/// indented again when written with the template
+struct Out<'a> {
+    output: &'a mut dyn Output,
+    level: usize,
+    at_line_start: bool,
+}
+
+impl<'a> Out<'a> {
+    fn new(output: &'a mut dyn Output) -> Self {
+        Out {
+            output,
+            level: 0,
+            at_line_start: true,
+        }
+    }
+
+    fn push(&mut self, txt: &str) -> Result<(), String> {
+        if self.level == 0 {
+            self.at_line_start = txt.ends_with('\n') || (self.at_line_start && txt.is_empty());
+            return self.output.write(txt);
+        }
+        for line in txt.split_inclusive('\n') {
+            if self.at_line_start && line != "\n" {
+                self.output.write(&INDENT.repeat(self.level))?;
             }
-            Node::Val(_) | Node::Transf2(_) | Node::Eof => (),
+            self.output.write(line)?;
+            self.at_line_start = line.ends_with('\n');
         }
-        Ok(self)
+        Ok(())
+    }
+
+    fn indent(&mut self) {
+        self.level += 1;
+    }
+
+    fn dedent(&mut self) {
+        self.level = self.level.saturating_sub(1);
     }
 }
 
 pub(crate) fn replace(
     ast: &Node,
-    fcallback: Option<&crate::FnCallBack>,
+    fcallback: &mut dyn ReplaceFn,
+    strict: bool,
 ) -> Result<Replaced, String> {
-    Ok(rec_replace(ast, fcallback, Replaced("".to_string()))?)
+    let mut replaced = String::new();
+    replace_to(ast, fcallback, strict, &mut replaced)?;
+    Ok(Replaced(replaced))
+}
+
+pub(crate) fn replace_to(
+    ast: &Node,
+    fcallback: &mut dyn ReplaceFn,
+    strict: bool,
+    out: &mut dyn Output,
+) -> Result<(), String> {
+    let mut replacer = Replacer {
+        fcallback,
+        strict,
+        counters: HashMap::new(),
+    };
+    rec_replace(ast, &mut replacer, "", &mut Out::new(out))
+}
+
+pub(crate) struct Replacer<'a> {
+    fcallback: &'a mut dyn ReplaceFn,
+    /// fail on missing names and unknown functions
+    /// instead of writing a mark on output
+    strict: bool,
+    /// `$(:counter key)`  last value by key
+    counters: HashMap<String, usize>,
+}
+
+/// where is applied a template, for error messages
+struct Location<'a> {
+    rule: &'a str,
+    template: &'a ReplTemplate,
+    pos: &'a Possition,
+}
+
+impl<'a> Location<'a> {
+    fn error(&self, msg: String) -> String {
+        format!(
+            "{}\n --> {}:{}\n = rule: {}\n = template: {}",
+            msg,
+            self.pos.row + 1,
+            self.pos.col + 1,
+            self.rule,
+            self.template
+        )
+    }
+}
+
+/// Information available to functions called from replace templates
+pub struct FnContext<'a> {
+    /// with all the values kept
+    replaced_nodes: &'a ReplacedNodes<'a>,
+}
+
+impl<'a> FnContext<'a> {
+    /// replaced text of a named node (or rule) on current template
+    /// if it's repeated, the last one
+    pub fn by_name(&self, name: &str) -> Option<&str> {
+        let p = self.replaced_nodes.get_by_name(name)?;
+        self.replaced_nodes.kept_value(p)
+    }
+
+    /// replaced text of all the occurrences of a named node (or rule)
+    pub fn all_by_name(&self, name: &str) -> Vec<&str> {
+        self.replaced_nodes
+            .all_by_name(name)
+            .filter_map(|p| self.replaced_nodes.kept_value(p))
+            .collect()
+    }
+
+    /// replaced text of a node by possition (1 based) on current template
+    pub fn by_pos(&self, pos: usize) -> Option<&str> {
+        let p = self.replaced_nodes.get_by_pos(pos)?;
+        self.replaced_nodes.kept_value(p)
+    }
+}
+
+/// No user functions, just the internal ones
+pub(crate) struct NoFn;
+
+impl ReplaceFn for NoFn {
+    fn call(&mut self, _: &str, _: &[&str], _: &FnContext) -> Result<Option<String>, String> {
+        Ok(None)
+    }
 }
 
 /// Replaced result
@@ -44,9 +268,7 @@ pub(crate) fn replace(
 pub struct Replaced(String);
 
 impl Replaced {
-    fn iappend(self, txt: &str) -> Self {
-        Self(self.0.iappend(txt))
-    }
+    /// replaced text
     pub fn str(&self) -> String {
         self.0.to_string()
     }
@@ -54,96 +276,250 @@ impl Replaced {
 
 fn rec_replace(
     ast: &Node,
-    fcallback: Option<&crate::FnCallBack>,
-    repl: Replaced,
-) -> Result<Replaced, String> {
+    replacer: &mut Replacer,
+    rule: &str,
+    out: &mut Out,
+) -> Result<(), String> {
     match ast {
-        Node::Eof => Ok(repl),
-        Node::Val(s) => Ok(repl.iappend(s)),
-        Node::Named((_, nodes)) => rec_replace_nodes(nodes, fcallback, repl),
-        Node::Transf2(crate::ast::Transf2 { template, nodes }) => {
-            rec_transf2_nodes(nodes, fcallback, template, repl)
+        Node::Eof(_) => Ok(()),
+        Node::Val((s, _)) => out.push(s),
+        Node::Named((_, nodes, _)) => rec_replace_nodes(nodes, replacer, rule, out),
+        Node::Transf2(crate::ast::Transf2 {
+            template,
+            nodes,
+            span,
+        }) => {
+            let location = Location {
+                rule,
+                template,
+                pos: span.start(),
+            };
+            //  the indentation of a template doesn't leak to the next ones
+            let level = out.level;
+            let result = rec_transf2_nodes(nodes, replacer, &location, out);
+            out.level = level;
+            result
         }
-        Node::Rule((_, nodes)) => rec_replace_nodes(nodes, fcallback, repl),
+        Node::Rule((name, nodes, _)) => rec_replace_nodes(nodes, replacer, name, out),
     }
 }
 
 fn rec_replace_nodes(
     nodes: &[Node],
-    fcallback: Option<&crate::FnCallBack>,
-    repl: Replaced,
-) -> Result<Replaced, String> {
-    nodes.iter().fold(Ok(repl), |acc, node| match acc {
-        Ok(repl) => rec_replace(node, fcallback, repl),
-        Err(e) => Err(e),
-    })
+    replacer: &mut Replacer,
+    rule: &str,
+    out: &mut Out,
+) -> Result<(), String> {
+    nodes
+        .iter()
+        .try_for_each(|node| rec_replace(node, replacer, rule, out))
 }
 
 fn rec_transf2_nodes(
     nodes: &[Node],
-    fcallback: Option<&crate::FnCallBack>,
-    template: &crate::parser::expression::ReplTemplate,
-    repl: Replaced,
-) -> Result<Replaced, String> {
-    if nodes.len() > 0 {
-        let replaced_nodes = nodes.iter().fold(Ok(ReplacedNodes::new()), |acc, node| {
-            acc?.process_node(node)
-        })?;
-        Ok(apply_transf2(fcallback, template, &replaced_nodes, repl))
+    replacer: &mut Replacer,
+    location: &Location,
+    out: &mut Out,
+) -> Result<(), String> {
+    if !nodes.is_empty() {
+        let mut replaced_nodes = ReplacedNodes::new(nodes);
+        apply_transf2(
+            replacer,
+            location.template,
+            location,
+            &mut replaced_nodes,
+            out,
+        )
     } else {
-        Ok(repl)
+        Ok(())
     }
 }
 
 fn apply_transf2(
-    fcallback: Option<&crate::FnCallBack>,
-    template: &crate::parser::expression::ReplTemplate,
-    replaced_nodes: &ReplacedNodes,
-    replaced: Replaced,
-) -> Replaced {
+    replacer: &mut Replacer,
+    template: &ReplTemplate,
+    location: &Location,
+    replaced_nodes: &mut ReplacedNodes,
+    out: &mut Out,
+) -> Result<(), String> {
     use crate::parser::expression::ReplItem;
 
-    template
-        .0
-        .iter()
-        .fold(replaced, |acc, repl_item| match repl_item {
-            ReplItem::Text(txt) => acc.iappend(txt),
-            ReplItem::ByPos(p) => match replaced_nodes.by_pos.get(*p) {
-                Some(rn) => acc.iappend(&format!("pos<{}/{}>", p, rn.0)),
-                None => acc.iappend(&format!("pos<{}/missing>", p)),
-            },
-            ReplItem::ByName(n) => match replaced_nodes.by_name.get(n) {
-                Some(rn) => acc.iappend(&format!("{}", rn.0)),
-                None => acc.iappend(&format!("name<{}/missing>", n)),
-            },
-            ReplItem::ByNameOpt(n) => match replaced_nodes.by_name.get(n) {
-                Some(rn) => acc.iappend(&format!("{}", rn.0)),
-                None => acc,
-            },
-            ReplItem::Function(f) => acc.iappend(&replace_fn(&f, fcallback)),
-        })
-}
-
-fn replace_fn(fn_name: &str, fcallback: Option<&crate::FnCallBack>) -> String {
-    if let Some(fc) = fcallback {
-        match fc.0(fn_name) {
-            Some(replaced) => replaced,
-            None => replace_internal_fn(fn_name),
+    let rule = location.rule;
+    template.0.iter().try_for_each(|repl_item| match repl_item {
+        ReplItem::Text(txt) => out.push(txt),
+        ReplItem::ByPos(p) => match replaced_nodes.get_by_pos(*p) {
+            Some(p) => replaced_nodes.write(p, replacer, rule, out),
+            None => Err(location.error(format!(
+                "position {} out of range on replace template, \
+                 there are {} elements (1 based)",
+                p,
+                replaced_nodes.by_pos.len()
+            ))),
+        },
+        ReplItem::ByName(n) => match (replaced_nodes.get_by_name(n), replacer.strict) {
+            (Some(p), _) => replaced_nodes.write(p, replacer, rule, out),
+            (None, false) => out.push(&format!("name<{}/missing>", n)),
+            (None, true) => Err(location.error(format!("missing name {}", n))),
+        },
+        ReplItem::ByNameOpt(n) => match replaced_nodes.get_by_name(n) {
+            Some(p) => replaced_nodes.write(p, replacer, rule, out),
+            None => Ok(()),
+        },
+        ReplItem::ByNameJoin(n, sep) => {
+            let positions = replaced_nodes.all_by_name(n).collect::<Vec<_>>();
+            positions.iter().enumerate().try_for_each(|(i, p)| {
+                if i > 0 {
+                    out.push(sep)?;
+                }
+                replaced_nodes.write(*p, replacer, rule, out)
+            })
         }
-    } else {
-        replace_internal_fn(fn_name)
+        ReplItem::ByNameCount(n) => out.push(&replaced_nodes.count_by_name(n).to_string()),
+        ReplItem::If(cond, then, else_) => {
+            let cond_value = match cond {
+                ReplCond::Named(n) => replaced_nodes.get_by_name(n).is_some(),
+                ReplCond::NamedEq(n, lit) => match replaced_nodes.get_by_name(n) {
+                    Some(p) => replaced_nodes.value(p, replacer, rule)? == lit.as_str(),
+                    None => false,
+                },
+            };
+            let block = if cond_value { then } else { else_ };
+            apply_transf2(replacer, block, location, replaced_nodes, out)
+        }
+        ReplItem::ByNameIndex(n, idx) => match replaced_nodes.get_by_name_index(n, idx) {
+            Some(p) => replaced_nodes.write(p, replacer, rule, out),
+            None => Err(location.error(format!(
+                "index {} out of range for name {} on replace template, \
+                 there are {} occurrences (1 based)",
+                idx,
+                n,
+                replaced_nodes.count_by_name(n)
+            ))),
+        },
+        ReplItem::Function(f) => {
+            replace_fn(f, replacer, replaced_nodes, rule, out).map_err(|e| location.error(e))
+        }
+    })
+}
+
+/// `fn_txt` is the function name followed by the arguments, separated by spaces
+/// ie: `pad 8 num`
+fn replace_fn(
+    fn_txt: &str,
+    replacer: &mut Replacer,
+    replaced_nodes: &mut ReplacedNodes,
+    rule: &str,
+    out: &mut Out,
+) -> Result<(), String> {
+    let mut words = fn_txt.split_whitespace();
+    let fn_name = words.next().unwrap_or("");
+    let args = words.collect::<Vec<_>>();
+
+    //  they change the output, not text, a callback cannot redefine them
+    match (fn_name, args.as_slice()) {
+        ("indent", []) => {
+            out.indent();
+            return Ok(());
+        }
+        ("dedent", []) => {
+            out.dedent();
+            return Ok(());
+        }
+        ("indent", _) | ("dedent", _) => {
+            return Err(format!("function {} doesn't receive arguments", fn_name))
+        }
+        _ => (),
+    }
+
+    //  functions can use the text of any node
+    replaced_nodes.keep_values(replacer, rule)?;
+    let replaced_nodes = &*replaced_nodes;
+    let context = FnContext { replaced_nodes };
+    match replacer.fcallback.call(fn_name, &args, &context) {
+        Ok(Some(replaced)) => out.push(&replaced),
+        Ok(None) => match (
+            replace_internal_fn(fn_name, &args, replacer, replaced_nodes)?,
+            replacer.strict,
+        ) {
+            (Some(replaced), _) => out.push(&replaced),
+            (None, false) => out.push(&format!("?unknown_fn?<{}>", fn_txt)),
+            (None, true) => Err(format!("unknown function {}", fn_txt)),
+        },
+        Err(e) => Err(format!("error calling function {}: {}", fn_txt, e)),
     }
 }
 
-fn replace_internal_fn(fn_name: &str) -> String {
+/// predefined functions receiving a name on the template  ie: `$(:upper name)`
+fn name_fn(fn_name: &str) -> Option<fn(&str) -> String> {
+    use crate::ast::functions;
+
+    match fn_name {
+        "upper" => Some(|t| t.to_uppercase()),
+        "lower" => Some(|t| t.to_lowercase()),
+        "camel" => Some(functions::camel_case),
+        "snake" => Some(functions::snake_case),
+        "trim" => Some(|t| t.trim().to_string()),
+        "len" => Some(|t| t.chars().count().to_string()),
+        "esc_rust" => Some(functions::escape_rust),
+        "esc_json" => Some(functions::escape_json),
+        "esc_xml" => Some(functions::escape_xml),
+        "esc_shell" => Some(functions::escape_shell),
+        _ => None,
+    }
+}
+
+/// name used by a predefined function on a template  ie: `upper name` -> `name`
+pub(crate) fn fn_name_arg(fn_txt: &str) -> Option<&str> {
+    let mut words = fn_txt.split_whitespace();
+    match (words.next().and_then(name_fn), words.next(), words.next()) {
+        (Some(_), Some(name), None) => Some(name),
+        _ => None,
+    }
+}
+
+/// predefined functions
+///
+/// Some of them receive a name on the template as argument  ie: `$(:upper name)`
+fn replace_internal_fn(
+    fn_name: &str,
+    args: &[&str],
+    replacer: &mut Replacer,
+    replaced_nodes: &ReplacedNodes,
+) -> Result<Option<String>, String> {
+    let strict = replacer.strict;
+    let with_arg = |f: fn(&str) -> String| match args {
+        [name] => match (
+            replaced_nodes
+                .get_by_name(name)
+                .and_then(|p| replaced_nodes.kept_value(p)),
+            strict,
+        ) {
+            (Some(txt), _) => Ok(Some(f(txt))),
+            (None, false) => Ok(Some(format!("name<{}/missing>", name))),
+            (None, true) => Err(format!("missing name {}", name)),
+        },
+        _ => Err(format!("function {} requires a name as argument", fn_name)),
+    };
+
+    if let Some(f) = name_fn(fn_name) {
+        return with_arg(f);
+    }
     match fn_name {
-        "none" => "".to_string(),
-        "endl" => "\n".to_string(),
-        "spc" => " ".to_string(),
-        "_" => " ".to_string(),
-        "tab" => "\t".to_string(),
-        "(" => "(".to_string(),
+        "none" => Ok(Some("".to_string())),
+        "endl" => Ok(Some("\n".to_string())),
+        "spc" => Ok(Some(" ".to_string())),
+        "_" => Ok(Some(" ".to_string())),
+        "tab" => Ok(Some("\t".to_string())),
+        "(" => Ok(Some("(".to_string())),
+        "rpar" => Ok(Some(")".to_string())),
+        "bar" => Ok(Some("|".to_string())),
+        "counter" => {
+            let key = args.first().copied().unwrap_or("");
+            let counter = replacer.counters.entry(key.to_string()).or_insert(0);
+            *counter += 1;
+            Ok(Some(counter.to_string()))
+        }
         // "now" => " ",
-        _ => format!("?unknown_fn?<{}>", fn_name),
+        _ => Ok(None),
     }
 }
@@ -0,0 +1,252 @@
+#![warn(missing_docs)]
+//! Support for the typed `ast` generated with `SetOfRules::typed_ast`
+//!
+//! Generated `TryFrom<&Node>` conversions call these functions.
+//! `Transf2` nodes are transparent, their nodes are taken as children
+//! of the rule (or named) node
+
+use crate::ast::{Node, Span, Transf2};
+use std::convert::TryFrom;
+
+#[cfg(test)]
+mod test;
+//  generated from rules_for_peg.peg (running the binary)
+#[cfg(test)]
+#[rustfmt::skip]
+#[allow(dead_code)] //  rules not reachable from main
+mod peg_ast;
+
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+//
+//  T Y P E S
+//
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+
+/// The node doesn't have the shape of the typed `ast`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Error {
+    /// what was expected  ie: `rule expr`
+    pub expected: String,
+    /// span of the node where it was expected
+    pub span: Span,
+}
+
+/// Shape of the nodes produced by an expression, to know which
+/// alternative of an `or` produced them
+#[derive(Debug)]
+pub enum Shape<'a> {
+    /// literal value
+    Lit(&'a str),
+    /// value of a char in the list or in a range
+    Match(&'a str, &'a [(char, char)]),
+    /// value of any char
+    Dot,
+    /// end of file
+    Eof,
+    /// no nodes (not, peek)
+    Empty,
+    /// never produces nodes (expected)
+    Never,
+    /// rule node
+    Rule(&'a str),
+    /// named node, with nodes of `shape`
+    Named(&'a str, &'a Shape<'a>),
+    /// sequence
+    And(&'a [Shape<'a>]),
+    /// first alternative matching
+    Or(&'a [Shape<'a>]),
+    /// repetition  (min, max)
+    Rep(&'a Shape<'a>, usize, Option<usize>),
+}
+
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+//
+//  A P I
+//
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+
+impl Error {
+    /// `expected` on `node`
+    pub fn new(node: &Node, expected: &str) -> Self {
+        Error {
+            expected: expected.to_owned(),
+            span: node.span().clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}",
+            self.span.start.row + 1,
+            self.span.start.col + 1,
+            self.expected
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// children of `node`, that has to be the rule `name`
+pub fn rule<'a>(node: &'a Node, name: &str) -> Result<Vec<&'a Node>, Error> {
+    match node {
+        Node::Rule((n, nodes, _)) if n == name => Ok(children(nodes)),
+        _ => Err(Error::new(node, &format!("rule {}", name))),
+    }
+}
+
+/// named nodes `name` on `nodes`
+pub fn named<'a>(nodes: &[&'a Node], name: &str) -> Vec<&'a Node> {
+    nodes
+        .iter()
+        .copied()
+        .filter(|n| matches!(n, Node::Named((nn, _, _)) if nn == name))
+        .collect()
+}
+
+/// rule nodes `name` on `nodes`
+pub fn rules<'a>(nodes: &[&'a Node], name: &str) -> Vec<&'a Node> {
+    nodes
+        .iter()
+        .copied()
+        .filter(|n| matches!(n, Node::Rule((nn, _, _)) if nn == name))
+        .collect()
+}
+
+/// the first of `found`, it's an error on `parent` if there is none
+pub fn one<'a>(parent: &Node, found: Vec<&'a Node>, name: &str) -> Result<&'a Node, Error> {
+    found
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::new(parent, name))
+}
+
+/// converted rule node inside a named node
+pub fn value<T>(named: &Node) -> Result<T, Error>
+where
+    T: for<'n> TryFrom<&'n Node, Error = Error>,
+{
+    match named {
+        Node::Named((name, nodes, _)) => match children(nodes).as_slice() {
+            [node] => T::try_from(node),
+            _ => Err(Error::new(named, &format!("a rule on {}", name))),
+        },
+        _ => T::try_from(named),
+    }
+}
+
+/// concatenated values under `node`
+pub fn text(node: &Node) -> String {
+    let mut acc = String::new();
+    push_text(&mut acc, node);
+    acc
+}
+
+/// concatenated values of `nodes`
+pub fn text_all(nodes: &[&Node]) -> String {
+    nodes.iter().fold(String::new(), |mut acc, node| {
+        push_text(&mut acc, node);
+        acc
+    })
+}
+
+/// `nodes` could be produced by an expression with `shape`
+pub fn matches(nodes: &[&Node], shape: &Shape) -> bool {
+    consume(shape, nodes, 0) == Some(nodes.len())
+}
+
+//-----------------------------------------------------------------------
+//  SUPPORT
+
+fn children(nodes: &[Node]) -> Vec<&Node> {
+    nodes.iter().fold(vec![], |mut acc, node| {
+        match node {
+            Node::Transf2(Transf2 { nodes, .. }) => acc.extend(children(nodes)),
+            _ => acc.push(node),
+        }
+        acc
+    })
+}
+
+fn push_text(acc: &mut String, node: &Node) {
+    match node {
+        Node::Val((v, _)) => acc.push_str(v),
+        Node::Rule((_, nodes, _))
+        | Node::Named((_, nodes, _))
+        | Node::Transf2(Transf2 { nodes, .. }) => nodes.iter().for_each(|n| push_text(acc, n)),
+        Node::Eof(_) => {}
+    }
+}
+
+//  position after consuming nodes from `i` with `shape`
+fn consume(shape: &Shape, nodes: &[&Node], i: usize) -> Option<usize> {
+    let val = |pred: &dyn Fn(&str) -> bool| match nodes.get(i) {
+        Some(Node::Val((v, _))) if pred(v) => Some(i + 1),
+        _ => None,
+    };
+    let single_char = |v: &str| {
+        let mut chars = v.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        }
+    };
+
+    match shape {
+        Shape::Lit(l) => val(&|v| v == *l),
+        Shape::Match(chars, bounds) => val(&|v| {
+            single_char(v).is_some_and(|ch| {
+                chars.contains(ch) || bounds.iter().any(|&(f, t)| f <= ch && ch <= t)
+            })
+        }),
+        Shape::Dot => val(&|v| single_char(v).is_some()),
+        Shape::Eof => match nodes.get(i) {
+            Some(Node::Eof(_)) => Some(i + 1),
+            _ => val(&|v| v == "EOF"),
+        },
+        Shape::Empty => Some(i),
+        Shape::Never => None,
+        Shape::Rule(name) => match nodes.get(i) {
+            Some(Node::Rule((n, _, _))) if n == name => Some(i + 1),
+            _ => None,
+        },
+        Shape::Named(name, shape) => match nodes.get(i) {
+            Some(Node::Named((n, nn, _))) if n == name && matches(&children(nn), shape) => {
+                Some(i + 1)
+            }
+            _ => None,
+        },
+        Shape::And(shapes) => shapes
+            .iter()
+            .try_fold(i, |i, shape| consume(shape, nodes, i)),
+        Shape::Or(shapes) => shapes.iter().find_map(|shape| consume(shape, nodes, i)),
+        Shape::Rep(shape, min, max) => {
+            let mut i = i;
+            let mut count = 0;
+            while max.is_none_or(|max| count < max) {
+                match consume(shape, nodes, i) {
+                    Some(next) if next != i => {
+                        i = next;
+                        count += 1;
+                    }
+                    Some(_) => {
+                        count += 1;
+                        break;
+                    }
+                    None => break,
+                }
+            }
+            if count >= *min {
+                Some(i)
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,1096 @@
+#[allow(unused_imports)]
+use std::convert::TryFrom;
+use yepp::typed;
+
+/// rule `main`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Main {
+    pub grammar: Box<Grammar>,
+}
+
+impl TryFrom<&yepp::Node> for Main {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "main")?;
+        Ok(Main {
+            grammar: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "grammar"), "rule grammar")?)?),
+        })
+    }
+}
+
+/// rule `grammar`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Grammar {
+    pub rule: Vec<Rule>,
+}
+
+impl TryFrom<&yepp::Node> for Grammar {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "grammar")?;
+        Ok(Grammar {
+            rule: typed::rules(&nodes, "rule").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// rule `symbol`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Symbol(pub String);
+
+impl TryFrom<&yepp::Node> for Symbol {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "symbol")?;
+        Ok(Symbol(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `rule`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Rule {
+    pub __: Vec<Rule4>,
+    pub rule_name: Box<RuleName>,
+    pub expr: Box<Expr>,
+    pub _eol: Box<Eol2>,
+    pub desc: String,
+}
+
+impl TryFrom<&yepp::Node> for Rule {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "rule")?;
+        Ok(Rule {
+            __: typed::rules(&nodes, "_").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            rule_name: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "rule_name"), "rule rule_name")?)?),
+            expr: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "expr"), "rule expr")?)?),
+            _eol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "_eol"), "rule _eol")?)?),
+            desc: typed::text(typed::one(node, typed::named(&nodes, "desc"), "named desc")?),
+        })
+    }
+}
+
+/// rule `rule_name`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct RuleName {
+    pub symbol: Box<Symbol>,
+}
+
+impl TryFrom<&yepp::Node> for RuleName {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "rule_name")?;
+        Ok(RuleName {
+            symbol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "symbol"), "rule symbol")?)?),
+        })
+    }
+}
+
+/// rule `rule_descr`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct RuleDescr {
+    pub __: Vec<Rule4>,
+    pub desc: String,
+}
+
+impl TryFrom<&yepp::Node> for RuleDescr {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "rule_descr")?;
+        Ok(RuleDescr {
+            __: typed::rules(&nodes, "_").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            desc: typed::text(typed::one(node, typed::named(&nodes, "desc"), "named desc")?),
+        })
+    }
+}
+
+/// rule `expr`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Expr {
+    pub or: Box<Or>,
+}
+
+impl TryFrom<&yepp::Node> for Expr {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "expr")?;
+        Ok(Expr {
+            or: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "or"), "rule or")?)?),
+        })
+    }
+}
+
+/// rule `or`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Or {
+    pub __: Vec<Rule4>,
+    pub and: Box<And>,
+    pub or: Option<Box<Or>>,
+}
+
+impl TryFrom<&yepp::Node> for Or {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "or")?;
+        Ok(Or {
+            __: typed::rules(&nodes, "_").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            and: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "and"), "rule and")?)?),
+            or: typed::rules(&nodes, "or").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+        })
+    }
+}
+
+/// rule `and`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum And {
+    Expected(Box<Expected>),
+    AndlineTransf2(Box<AndlineTransf2>),
+    JustAndline(Box<JustAndline>),
+}
+
+impl TryFrom<&yepp::Node> for And {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "and")?;
+        if typed::matches(&nodes, &typed::Shape::Rule("expected")) {
+            return Ok(And::Expected(Box::new(Expected::try_from(nodes[0])?)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("andline_transf2")) {
+            return Ok(And::AndlineTransf2(Box::new(AndlineTransf2::try_from(nodes[0])?)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("just_andline")) {
+            return Ok(And::JustAndline(Box::new(JustAndline::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule and"))
+    }
+}
+
+/// rule `andline_transf2`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct AndlineTransf2 {
+    pub andline: Box<Andline>,
+    pub transf2: Box<Transf2>,
+    pub and: String,
+}
+
+impl TryFrom<&yepp::Node> for AndlineTransf2 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "andline_transf2")?;
+        Ok(AndlineTransf2 {
+            andline: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "andline"), "rule andline")?)?),
+            transf2: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "transf2"), "rule transf2")?)?),
+            and: typed::text(typed::one(node, typed::named(&nodes, "and"), "named and")?),
+        })
+    }
+}
+
+/// rule `just_andline`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct JustAndline {
+    pub andline: Box<Andline>,
+    pub _eol: Option<Box<Eol2>>,
+    pub eol: Vec<Eol>,
+    pub __: Option<Box<Rule4>>,
+    pub no_rule_name: Option<Box<NoRuleName>>,
+    pub and: Option<Box<And>>,
+}
+
+impl TryFrom<&yepp::Node> for JustAndline {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "just_andline")?;
+        Ok(JustAndline {
+            andline: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "andline"), "rule andline")?)?),
+            _eol: typed::rules(&nodes, "_eol").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            eol: typed::rules(&nodes, "eol").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            __: typed::rules(&nodes, "_").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            no_rule_name: typed::rules(&nodes, "no_rule_name").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            and: typed::rules(&nodes, "and").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+        })
+    }
+}
+
+/// rule `no_rule_name`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct NoRuleName(pub String);
+
+impl TryFrom<&yepp::Node> for NoRuleName {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "no_rule_name")?;
+        Ok(NoRuleName(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `expected`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Expected {
+    pub __: Vec<Rule4>,
+    pub literal: Box<Literal>,
+}
+
+impl TryFrom<&yepp::Node> for Expected {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "expected")?;
+        Ok(Expected {
+            __: typed::rules(&nodes, "_").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            literal: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "literal"), "rule literal")?)?),
+        })
+    }
+}
+
+/// rule `andline`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Andline {
+    pub andchunk: Vec<Andchunk>,
+    pub expected: Vec<Expected>,
+}
+
+impl TryFrom<&yepp::Node> for Andline {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "andline")?;
+        Ok(Andline {
+            andchunk: typed::rules(&nodes, "andchunk").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            expected: typed::rules(&nodes, "expected").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// rule `andchunk`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Andchunk {
+    Alt1 {
+        name: Box<Name>,
+        e: Box<RepOrUnary>,
+    },
+    RepOrUnary(Box<RepOrUnary>),
+}
+
+impl TryFrom<&yepp::Node> for Andchunk {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "andchunk")?;
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Rule("name"), typed::Shape::Named("e", &typed::Shape::Rule("rep_or_unary"))])) {
+            return Ok(Andchunk::Alt1 {
+                name: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "name"), "rule name")?)?),
+                e: Box::new(typed::value(typed::one(node, typed::named(&nodes, "e"), "named e")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("rep_or_unary")) {
+            return Ok(Andchunk::RepOrUnary(Box::new(RepOrUnary::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule andchunk"))
+    }
+}
+
+/// rule `_1`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Rule1 {
+    Alt1(String),
+    Eol(Box<Eol>),
+}
+
+impl TryFrom<&yepp::Node> for Rule1 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "_1")?;
+        if typed::matches(&nodes, &typed::Shape::Lit(" ")) {
+            return Ok(Rule1::Alt1(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("eol")) {
+            return Ok(Rule1::Eol(Box::new(Eol::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule _1"))
+    }
+}
+
+/// rule `rep_or_unary`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum RepOrUnary {
+    Alt1 {
+        atom_or_par: Box<AtomOrPar>,
+        rep_symbol: Option<Box<RepSymbol>>,
+    },
+    Alt2 {
+        atom_or_par: Box<AtomOrPar>,
+    },
+    Alt3 {
+        atom_or_par: Box<AtomOrPar>,
+    },
+}
+
+impl TryFrom<&yepp::Node> for RepOrUnary {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "rep_or_unary")?;
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Rule("atom_or_par"), typed::Shape::Rep(&typed::Shape::Rule("rep_symbol"), 0, Some(1))])) {
+            return Ok(RepOrUnary::Alt1 {
+                atom_or_par: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "atom_or_par"), "rule atom_or_par")?)?),
+                rep_symbol: typed::rules(&nodes, "rep_symbol").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Lit("!"), typed::Shape::Rule("atom_or_par")])) {
+            return Ok(RepOrUnary::Alt2 {
+                atom_or_par: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "atom_or_par"), "rule atom_or_par")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Lit("&"), typed::Shape::Rule("atom_or_par")])) {
+            return Ok(RepOrUnary::Alt3 {
+                atom_or_par: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "atom_or_par"), "rule atom_or_par")?)?),
+            });
+        }
+        Err(typed::Error::new(node, "an alternative of rule rep_or_unary"))
+    }
+}
+
+/// rule `rep_symbol`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum RepSymbol {
+    Alt1(String),
+    Alt2(String),
+    Alt3(String),
+}
+
+impl TryFrom<&yepp::Node> for RepSymbol {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "rep_symbol")?;
+        if typed::matches(&nodes, &typed::Shape::Lit("*")) {
+            return Ok(RepSymbol::Alt1(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("+")) {
+            return Ok(RepSymbol::Alt2(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("?")) {
+            return Ok(RepSymbol::Alt3(typed::text_all(&nodes)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule rep_symbol"))
+    }
+}
+
+/// rule `atom_or_par`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum AtomOrPar {
+    Atom(Box<Atom>),
+    Parenth(Box<Parenth>),
+}
+
+impl TryFrom<&yepp::Node> for AtomOrPar {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "atom_or_par")?;
+        if typed::matches(&nodes, &typed::Shape::Rule("atom")) {
+            return Ok(AtomOrPar::Atom(Box::new(Atom::try_from(nodes[0])?)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("parenth")) {
+            return Ok(AtomOrPar::Parenth(Box::new(Parenth::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule atom_or_par"))
+    }
+}
+
+/// rule `parenth`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Parenth {
+    pub __: Vec<Rule4>,
+    pub expr: Box<Expr>,
+}
+
+impl TryFrom<&yepp::Node> for Parenth {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "parenth")?;
+        Ok(Parenth {
+            __: typed::rules(&nodes, "_").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            expr: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "expr"), "rule expr")?)?),
+        })
+    }
+}
+
+/// rule `atom`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Atom {
+    Alt1 {
+        a: Box<Literal>,
+    },
+    Alt2 {
+        a: Box<Match>,
+    },
+    Alt3 {
+        a: Box<RuleName>,
+    },
+    Dot(Box<Dot>),
+}
+
+impl TryFrom<&yepp::Node> for Atom {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "atom")?;
+        if typed::matches(&nodes, &typed::Shape::Named("a", &typed::Shape::Rule("literal"))) {
+            return Ok(Atom::Alt1 {
+                a: Box::new(typed::value(typed::one(node, typed::named(&nodes, "a"), "named a")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::Named("a", &typed::Shape::Rule("match"))) {
+            return Ok(Atom::Alt2 {
+                a: Box::new(typed::value(typed::one(node, typed::named(&nodes, "a"), "named a")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::Named("a", &typed::Shape::Rule("rule_name"))) {
+            return Ok(Atom::Alt3 {
+                a: Box::new(typed::value(typed::one(node, typed::named(&nodes, "a"), "named a")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("dot")) {
+            return Ok(Atom::Dot(Box::new(Dot::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule atom"))
+    }
+}
+
+/// rule `literal`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Literal {
+    LitNoesc(Box<LitNoesc>),
+    LitEsc(Box<LitEsc>),
+}
+
+impl TryFrom<&yepp::Node> for Literal {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "literal")?;
+        if typed::matches(&nodes, &typed::Shape::Rule("lit_noesc")) {
+            return Ok(Literal::LitNoesc(Box::new(LitNoesc::try_from(nodes[0])?)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("lit_esc")) {
+            return Ok(Literal::LitEsc(Box::new(LitEsc::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule literal"))
+    }
+}
+
+/// rule `lit_noesc`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct LitNoesc {
+    pub __u27_: Vec<Rule2>,
+    pub l: String,
+}
+
+impl TryFrom<&yepp::Node> for LitNoesc {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "lit_noesc")?;
+        Ok(LitNoesc {
+            __u27_: typed::rules(&nodes, "_'").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            l: typed::text(typed::one(node, typed::named(&nodes, "l"), "named l")?),
+        })
+    }
+}
+
+/// rule `_'`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Rule2(pub String);
+
+impl TryFrom<&yepp::Node> for Rule2 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "_'")?;
+        Ok(Rule2(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `lit_esc`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct LitEsc {
+    pub __u22_: Vec<Rule3>,
+    pub l: String,
+}
+
+impl TryFrom<&yepp::Node> for LitEsc {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "lit_esc")?;
+        Ok(LitEsc {
+            __u22_: typed::rules(&nodes, "_\"").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            l: typed::text(typed::one(node, typed::named(&nodes, "l"), "named l")?),
+        })
+    }
+}
+
+/// rule `_"`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Rule3(pub String);
+
+impl TryFrom<&yepp::Node> for Rule3 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "_\"")?;
+        Ok(Rule3(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `esc_char`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum EscChar {
+    Alt1(String),
+    Alt2(String),
+    Alt3(String),
+    Alt4(String),
+    Alt5(String),
+}
+
+impl TryFrom<&yepp::Node> for EscChar {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "esc_char")?;
+        if typed::matches(&nodes, &typed::Shape::Lit("\\r")) {
+            return Ok(EscChar::Alt1(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\n")) {
+            return Ok(EscChar::Alt2(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\t")) {
+            return Ok(EscChar::Alt3(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\\\")) {
+            return Ok(EscChar::Alt4(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\\\\"")) {
+            return Ok(EscChar::Alt5(typed::text_all(&nodes)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule esc_char"))
+    }
+}
+
+/// rule `hex_char`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct HexChar(pub String);
+
+impl TryFrom<&yepp::Node> for HexChar {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "hex_char")?;
+        Ok(HexChar(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `eol`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Eol {
+    Alt1(String),
+    Alt2(String),
+    Alt3(String),
+}
+
+impl TryFrom<&yepp::Node> for Eol {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "eol")?;
+        if typed::matches(&nodes, &typed::Shape::Lit("\\r\\n")) {
+            return Ok(Eol::Alt1(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\n")) {
+            return Ok(Eol::Alt2(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("\\r")) {
+            return Ok(Eol::Alt3(typed::text_all(&nodes)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule eol"))
+    }
+}
+
+/// rule `_eol`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Eol2 {
+    pub comment: Vec<Comment>,
+    pub eol: Box<Eol>,
+}
+
+impl TryFrom<&yepp::Node> for Eol2 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "_eol")?;
+        Ok(Eol2 {
+            comment: typed::rules(&nodes, "comment").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            eol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "eol"), "rule eol")?)?),
+        })
+    }
+}
+
+/// rule `match`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Match {
+    pub mchars: Option<Box<Mchars>>,
+    pub b: Vec<String>,
+}
+
+impl TryFrom<&yepp::Node> for Match {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "match")?;
+        Ok(Match {
+            mchars: typed::rules(&nodes, "mchars").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            b: typed::named(&nodes, "b").into_iter().map(typed::text).collect(),
+        })
+    }
+}
+
+/// rule `mchars`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Mchars(pub String);
+
+impl TryFrom<&yepp::Node> for Mchars {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "mchars")?;
+        Ok(Mchars(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `mbetween`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Mbetween {
+    pub f: String,
+    pub s: String,
+}
+
+impl TryFrom<&yepp::Node> for Mbetween {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "mbetween")?;
+        Ok(Mbetween {
+            f: typed::text(typed::one(node, typed::named(&nodes, "f"), "named f")?),
+            s: typed::text(typed::one(node, typed::named(&nodes, "s"), "named s")?),
+        })
+    }
+}
+
+/// rule `dot`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Dot(pub String);
+
+impl TryFrom<&yepp::Node> for Dot {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "dot")?;
+        Ok(Dot(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `_`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Rule4 {
+    pub eol: Vec<Eol>,
+    pub comment: Vec<Comment>,
+}
+
+impl TryFrom<&yepp::Node> for Rule4 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "_")?;
+        Ok(Rule4 {
+            eol: typed::rules(&nodes, "eol").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            comment: typed::rules(&nodes, "comment").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// rule `comment`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum Comment {
+    LineComment(Box<LineComment>),
+    MlineComment(Box<MlineComment>),
+}
+
+impl TryFrom<&yepp::Node> for Comment {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "comment")?;
+        if typed::matches(&nodes, &typed::Shape::Rule("line_comment")) {
+            return Ok(Comment::LineComment(Box::new(LineComment::try_from(nodes[0])?)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rule("mline_comment")) {
+            return Ok(Comment::MlineComment(Box::new(MlineComment::try_from(nodes[0])?)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule comment"))
+    }
+}
+
+/// rule `line_comment`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct LineComment(pub String);
+
+impl TryFrom<&yepp::Node> for LineComment {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "line_comment")?;
+        Ok(LineComment(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `mline_comment`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct MlineComment(pub String);
+
+impl TryFrom<&yepp::Node> for MlineComment {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "mline_comment")?;
+        Ok(MlineComment(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `name`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Name {
+    pub symbol: Box<Symbol>,
+}
+
+impl TryFrom<&yepp::Node> for Name {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "name")?;
+        Ok(Name {
+            symbol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "symbol"), "rule symbol")?)?),
+        })
+    }
+}
+
+/// rule `transf2`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct Transf2 {
+    pub _1: Box<Rule1>,
+    pub __: Box<Rule4>,
+    pub transf_rule: Box<TransfRule>,
+}
+
+impl TryFrom<&yepp::Node> for Transf2 {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "transf2")?;
+        Ok(Transf2 {
+            _1: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "_1"), "rule _1")?)?),
+            __: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "_"), "rule _")?)?),
+            transf_rule: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "transf_rule"), "rule transf_rule")?)?),
+        })
+    }
+}
+
+/// rule `transf_rule`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TransfRule {
+    pub tmpl_text: Vec<TmplText>,
+    pub tmpl_rule: Vec<TmplRule>,
+}
+
+impl TryFrom<&yepp::Node> for TransfRule {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "transf_rule")?;
+        Ok(TransfRule {
+            tmpl_text: typed::rules(&nodes, "tmpl_text").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            tmpl_rule: typed::rules(&nodes, "tmpl_rule").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// rule `tmpl_text`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplText {
+    pub t: String,
+}
+
+impl TryFrom<&yepp::Node> for TmplText {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_text")?;
+        Ok(TmplText {
+            t: typed::text(typed::one(node, typed::named(&nodes, "t"), "named t")?),
+        })
+    }
+}
+
+/// rule `tmpl_rule`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplRule {
+    pub tmpl_if: Option<Box<TmplIf>>,
+    pub symbol: Vec<Symbol>,
+    pub sep: Option<String>,
+    pub idx: Option<Box<TmplIdx>>,
+    pub pos: Option<String>,
+    pub r#fn: Option<String>,
+}
+
+impl TryFrom<&yepp::Node> for TmplRule {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_rule")?;
+        Ok(TmplRule {
+            tmpl_if: typed::rules(&nodes, "tmpl_if").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            symbol: typed::rules(&nodes, "symbol").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            sep: typed::named(&nodes, "sep").into_iter().next().map(typed::text),
+            idx: typed::named(&nodes, "idx").into_iter().next().map(typed::value).transpose()?.map(Box::new),
+            pos: typed::named(&nodes, "pos").into_iter().next().map(typed::text),
+            r#fn: typed::named(&nodes, "fn").into_iter().next().map(typed::text),
+        })
+    }
+}
+
+/// rule `tmpl_if`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplIf {
+    pub cond: Box<TmplCond>,
+    pub then: Box<TmplBlock>,
+    pub r#else: String,
+}
+
+impl TryFrom<&yepp::Node> for TmplIf {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_if")?;
+        Ok(TmplIf {
+            cond: Box::new(typed::value(typed::one(node, typed::named(&nodes, "cond"), "named cond")?)?),
+            then: Box::new(typed::value(typed::one(node, typed::named(&nodes, "then"), "named then")?)?),
+            r#else: typed::text(typed::one(node, typed::named(&nodes, "else"), "named else")?),
+        })
+    }
+}
+
+/// rule `tmpl_cond`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum TmplCond {
+    Alt1 {
+        symbol: Box<Symbol>,
+        l: Box<TmplLit>,
+    },
+    Alt2 {
+        symbol: Box<Symbol>,
+    },
+}
+
+impl TryFrom<&yepp::Node> for TmplCond {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_cond")?;
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Rule("symbol"), typed::Shape::Rep(&typed::Shape::Lit(" "), 0, None), typed::Shape::Lit("=="), typed::Shape::Rep(&typed::Shape::Lit(" "), 0, None), typed::Shape::Lit("\""), typed::Shape::Named("l", &typed::Shape::Rule("tmpl_lit")), typed::Shape::Lit("\""), typed::Shape::Rep(&typed::Shape::Lit(" "), 0, None)])) {
+            return Ok(TmplCond::Alt1 {
+                symbol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "symbol"), "rule symbol")?)?),
+                l: Box::new(typed::value(typed::one(node, typed::named(&nodes, "l"), "named l")?)?),
+            });
+        }
+        if typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Rule("symbol"), typed::Shape::Rep(&typed::Shape::Lit(" "), 0, None)])) {
+            return Ok(TmplCond::Alt2 {
+                symbol: Box::new(typed::value(typed::one(node, typed::rules(&nodes, "symbol"), "rule symbol")?)?),
+            });
+        }
+        Err(typed::Error::new(node, "an alternative of rule tmpl_cond"))
+    }
+}
+
+/// rule `tmpl_else`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplElse {
+    pub b: Box<TmplBlock>,
+}
+
+impl TryFrom<&yepp::Node> for TmplElse {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_else")?;
+        Ok(TmplElse {
+            b: Box::new(typed::value(typed::one(node, typed::named(&nodes, "b"), "named b")?)?),
+        })
+    }
+}
+
+/// rule `tmpl_block`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplBlock {
+    pub tmpl_block_text: Vec<TmplBlockText>,
+    pub tmpl_rule: Vec<TmplRule>,
+}
+
+impl TryFrom<&yepp::Node> for TmplBlock {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_block")?;
+        Ok(TmplBlock {
+            tmpl_block_text: typed::rules(&nodes, "tmpl_block_text").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+            tmpl_rule: typed::rules(&nodes, "tmpl_rule").into_iter().map(typed::value).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// rule `tmpl_block_text`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplBlockText {
+    pub t: String,
+}
+
+impl TryFrom<&yepp::Node> for TmplBlockText {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_block_text")?;
+        Ok(TmplBlockText {
+            t: typed::text(typed::one(node, typed::named(&nodes, "t"), "named t")?),
+        })
+    }
+}
+
+/// rule `tmpl_lit`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplLit(pub String);
+
+impl TryFrom<&yepp::Node> for TmplLit {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_lit")?;
+        Ok(TmplLit(typed::text_all(&nodes)))
+    }
+}
+
+/// rule `tmpl_sep`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct TmplSep {
+    pub s: Box<TmplLit>,
+}
+
+impl TryFrom<&yepp::Node> for TmplSep {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_sep")?;
+        Ok(TmplSep {
+            s: Box::new(typed::value(typed::one(node, typed::named(&nodes, "s"), "named s")?)?),
+        })
+    }
+}
+
+/// rule `tmpl_idx`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum TmplIdx {
+    Alt1(String),
+    Alt2(String),
+    Alt3(String),
+}
+
+impl TryFrom<&yepp::Node> for TmplIdx {
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {
+        let nodes = typed::rule(node, "tmpl_idx")?;
+        if typed::matches(&nodes, &typed::Shape::Lit("first")) {
+            return Ok(TmplIdx::Alt1(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Lit("last")) {
+            return Ok(TmplIdx::Alt2(typed::text_all(&nodes)));
+        }
+        if typed::matches(&nodes, &typed::Shape::Rep(&typed::Shape::Match("", &[('0', '9')]), 1, None)) {
+            return Ok(TmplIdx::Alt3(typed::text_all(&nodes)));
+        }
+        Err(typed::Error::new(node, "an alternative of rule tmpl_idx"))
+    }
+}
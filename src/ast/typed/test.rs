@@ -0,0 +1,140 @@
+//-----------------------------------------------------------------------
+//
+//  mod typed  TEST
+//
+//-----------------------------------------------------------------------
+
+use super::peg_ast;
+use crate::Peg;
+use std::convert::TryFrom;
+
+//  rules as the interpreter runs them, the end of line is the escaped `\n`
+fn peg_rules() -> crate::SetOfRules {
+    Peg::new(include_str!("../../rules_for_peg.peg"))
+        .gen_rules()
+        .unwrap()
+}
+
+fn find_rules<'a>(node: &'a crate::Node, name: &str, found: &mut Vec<&'a crate::Node>) {
+    match node {
+        crate::Node::Rule((n, nodes, _)) => {
+            if n == name {
+                found.push(node);
+            }
+            nodes.iter().for_each(|n| find_rules(n, name, found));
+        }
+        crate::Node::Named((_, nodes, _)) => nodes.iter().for_each(|n| find_rules(n, name, found)),
+        crate::Node::Transf2(t2) => t2.nodes.iter().for_each(|n| find_rules(n, name, found)),
+        crate::Node::Val(_) | crate::Node::Eof(_) => (),
+    }
+}
+
+#[test]
+fn test_typed_peg_ast() {
+    let peg = r#"main = 'a' / b\nb = "b"+ [0-9]\n"#;
+    let ast = peg_rules().parse(peg).unwrap();
+    let main = peg_ast::Main::try_from(&ast).unwrap();
+
+    let names = main
+        .grammar
+        .rule
+        .iter()
+        .map(|r| r.rule_name.symbol.0.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["main", "b"]);
+}
+
+#[test]
+fn test_typed_peg_ast_escaped_literals() {
+    let peg = r#"main = "\n" '\t' "\\" [\r]\r\n"#;
+    let ast = peg_rules().parse(peg).unwrap();
+    assert!(peg_ast::Main::try_from(&ast).is_ok());
+
+    let mut esc_chars = vec![];
+    find_rules(&ast, "esc_char", &mut esc_chars);
+    let esc_chars = esc_chars
+        .into_iter()
+        .map(|node| peg_ast::EscChar::try_from(node).unwrap())
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        esc_chars.as_slice(),
+        [peg_ast::EscChar::Alt2(nl), peg_ast::EscChar::Alt4(bs)] if nl == "\\n" && bs == "\\\\"
+    ));
+
+    let mut eols = vec![];
+    find_rules(&ast, "eol", &mut eols);
+    assert_eq!(
+        eols.into_iter()
+            .map(|node| peg_ast::Eol::try_from(node).unwrap())
+            .collect::<Vec<_>>(),
+        vec![peg_ast::Eol::Alt1("\\r\\n".to_string())]
+    );
+}
+
+#[test]
+fn test_typed_wrong_node() {
+    let ast = peg_rules().parse(r#"main = 'a'\n"#).unwrap();
+    let node = match &ast {
+        crate::Node::Rule((_, nodes, _)) => &nodes[0],
+        _ => panic!("expected rule node"),
+    };
+
+    assert_eq!(
+        peg_ast::Main::try_from(node).unwrap_err().to_string(),
+        "1:1: expected rule main"
+    );
+}
+
+#[test]
+fn test_typed_code() {
+    let code = Peg::new(
+        r#"
+        main    =   first:item  (',' item)*  end?
+        item    =   n:num  /  '(' main ')'  /  'x'
+        num     =   [0-9]+
+        end     =   ';'
+        string  =   '"' (!'"' .)* '"'
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .typed_ast()
+    .unwrap();
+
+    assert!(code.contains(
+        "pub struct Main {
+    pub first: Box<Item>,
+    pub item: Vec<Item>,
+    pub end: Option<Box<End>>,
+}"
+    ));
+    assert!(code.contains(
+        "pub enum Item {
+    Alt1 {
+        n: Box<Num>,
+    },
+    Alt2 {
+        main: Box<Main>,
+    },
+    Alt3(String),
+}"
+    ));
+    assert!(code.contains("pub struct Num(pub String);"));
+    assert!(code.contains("pub struct StringRule(pub String);"));
+    assert!(code.contains("impl TryFrom<&yepp::Node> for Item {"));
+    assert!(code.contains(
+        r#"typed::matches(&nodes, &typed::Shape::And(&[typed::Shape::Lit("("), typed::Shape::Rule("main"), typed::Shape::Lit(")")]))"#
+    ));
+}
+
+#[test]
+fn test_typed_undefined_rule() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: ref_rule!("a"), descr: None}
+    };
+
+    assert!(matches!(
+        rules.typed_ast(),
+        Err(crate::Error::GrammarErr(_))
+    ));
+}
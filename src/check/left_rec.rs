@@ -0,0 +1,135 @@
+//! Left recursion analysis
+//!
+//! A rule is left recursive if it can call itself without consuming input.
+//! It's supported while parsing (growing a seed), but the seed has to come
+//! from an alternative without the recursion
+
+use super::{nullable, sub_expressions, Issue, IssueKind};
+use crate::parser::atom::Atom;
+use crate::parser::expression::{Expression, MetaExpr, NamedExpr, SetOfRules, Transf2Expr};
+use std::collections::{HashMap, HashSet};
+
+/// left recursive cycles, as a path of rules
+/// the path starts with the lower rule name and doesn't repeat it at the end
+pub(crate) fn left_recursions(rules: &SetOfRules) -> Vec<Vec<String>> {
+    let nullables = nullable::nullable_rules(rules);
+    let graph = rules
+        .0
+        .iter()
+        .map(|(name, ri)| {
+            let mut refs = left_refs(&ri.expr, &nullables);
+            refs.sort_unstable();
+            refs.dedup();
+            (name.as_str(), refs)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut names = graph.keys().copied().collect::<Vec<_>>();
+    names.sort_unstable();
+
+    let mut cycles = vec![];
+    for start in names {
+        find_cycles(&graph, start, &mut vec![start], &mut cycles);
+    }
+    cycles
+}
+
+/// left recursive cycles where no rule can produce a seed
+pub(crate) fn check_left_recursions(rules: &SetOfRules) -> Vec<Issue> {
+    let productives = productive_rules(rules);
+    left_recursions(rules)
+        .into_iter()
+        .filter(|cycle| cycle.iter().any(|r| !productives.contains(r.as_str())))
+        .map(|cycle| Issue {
+            rule: Some(cycle[0].clone()),
+            kind: IssueKind::LeftRecursion(cycle),
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------
+//  S U P P O R T
+
+/// rules that can be called without consuming input
+fn left_refs<'a>(expr: &'a Expression, nullables: &HashSet<&str>) -> Vec<&'a str> {
+    let left_refs_seq = |exprs: &'a [Expression]| {
+        let mut refs = vec![];
+        for e in exprs {
+            refs.extend(left_refs(e, nullables));
+            if !nullable::is_nullable(e, nullables) {
+                break;
+            }
+        }
+        refs
+    };
+
+    match expr {
+        Expression::RuleName(name) => vec![name.as_str()],
+        Expression::And(mexpr) => left_refs_seq(&mexpr.0),
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            left_refs_seq(&mexpr.0)
+        }
+        _ => sub_expressions(expr)
+            .into_iter()
+            .flat_map(|e| left_refs(e, nullables))
+            .collect(),
+    }
+}
+
+/// elementary cycles starting on `path[0]`, visiting only greater rules
+/// in order to report every cycle once
+fn find_cycles<'a>(
+    graph: &HashMap<&'a str, Vec<&'a str>>,
+    rule: &str,
+    path: &mut Vec<&'a str>,
+    cycles: &mut Vec<Vec<String>>,
+) {
+    let start = path[0];
+    for &next in graph.get(rule).into_iter().flatten() {
+        if next == start {
+            cycles.push(path.iter().map(|r| r.to_string()).collect());
+        } else if next > start && !path.contains(&next) {
+            path.push(next);
+            find_cycles(graph, next, path, cycles);
+            path.pop();
+        }
+    }
+}
+
+/// rules that can succeed
+/// computed as a fixed point, starting with none of them productive
+fn productive_rules(rules: &SetOfRules) -> HashSet<&str> {
+    let mut productives = HashSet::new();
+    loop {
+        let new_productives = rules
+            .0
+            .iter()
+            .filter(|(name, ri)| {
+                !productives.contains(name.as_str()) && is_productive(&ri.expr, &productives)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        if new_productives.is_empty() {
+            return productives;
+        }
+        productives.extend(new_productives);
+    }
+}
+
+fn is_productive(expr: &Expression, productives: &HashSet<&str>) -> bool {
+    match expr {
+        Expression::Simple(Atom::Expected(_)) => false,
+        Expression::Simple(_) | Expression::Not(_) => true,
+        Expression::And(mexpr) => mexpr.0.iter().all(|e| is_productive(e, productives)),
+        Expression::Or(mexpr) => mexpr.0.iter().any(|e| is_productive(e, productives)),
+        Expression::Peek(e) => is_productive(e, productives),
+        Expression::Repeat(rep) => rep.min.0 == 0 || is_productive(&rep.expression, productives),
+        Expression::RuleName(name) => productives.contains(name.as_str()),
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => {
+            is_productive(expr, productives)
+        }
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().all(|e| is_productive(e, productives))
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Grammar lints
+//!
+//! Not errors, the grammar works, but probably it isn't what you want
+
+use super::{rule_refs, sub_expressions, Issue, IssueKind};
+use crate::parser::atom::Atom;
+use crate::parser::expression::{Expression, MetaExpr, NamedExpr, SetOfRules, Transf2Expr};
+use std::collections::HashSet;
+
+/// rules not reachable from `start` and alternatives that never match
+pub(crate) fn lint(rules: &SetOfRules, start: &str) -> Vec<Issue> {
+    let reachables = reachable_rules(rules, start);
+
+    let mut names = rules.0.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let unused = names
+        .iter()
+        .filter(|name| !reachables.contains(name.as_str()))
+        .map(|name| Issue {
+            rule: Some(name.to_string()),
+            kind: IssueKind::UnusedRule,
+        });
+
+    let unreachable_alts = names.iter().flat_map(|name| {
+        let mut alts = vec![];
+        unreachable_alternatives(&rules.0[*name].expr, &mut alts);
+        alts.into_iter().map(move |n| Issue {
+            rule: Some(name.to_string()),
+            kind: IssueKind::UnreachableAlternative(n),
+        })
+    });
+
+    unused.chain(unreachable_alts).collect()
+}
+
+// -------------------------------------------------------------------------------------
+//  S U P P O R T
+
+fn reachable_rules<'a>(rules: &'a SetOfRules, start: &'a str) -> HashSet<&'a str> {
+    let mut reachables = HashSet::new();
+    let mut pending = vec![start];
+    while let Some(name) = pending.pop() {
+        if let Some((name, ri)) = rules.0.get_key_value(name) {
+            if reachables.insert(name.as_str()) {
+                pending.extend(rule_refs(&ri.expr));
+            }
+        }
+    }
+    reachables
+}
+
+/// alternatives (1 based) of `or` expressions never tried
+/// because a previous one always matches first
+fn unreachable_alternatives(expr: &Expression, alts: &mut Vec<usize>) {
+    if let Expression::Or(mexpr) = expr {
+        for (n, alt) in mexpr.0.iter().enumerate() {
+            let prev = &mexpr.0[..n];
+            let prefix = text_prefix(alt);
+            let shadowed = prev.iter().any(always_matches)
+                || prev
+                    .iter()
+                    .filter_map(fixed_text)
+                    .any(|t| prefix.starts_with(&t));
+            if shadowed {
+                alts.push(n + 1);
+            }
+        }
+    }
+    for e in sub_expressions(expr) {
+        unreachable_alternatives(e, alts);
+    }
+}
+
+/// the expression only matches this text
+fn fixed_text(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Simple(Atom::Literal(l)) => Some(l.clone()),
+        Expression::And(mexpr)
+        | Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().map(fixed_text).collect()
+        }
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => fixed_text(expr),
+        _ => None,
+    }
+}
+
+/// any text matched by the expression, starts with this one
+fn text_prefix(expr: &Expression) -> String {
+    match expr {
+        Expression::Simple(Atom::Literal(l)) => l.clone(),
+        Expression::And(mexpr)
+        | Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            let mut prefix = String::new();
+            for e in &mexpr.0 {
+                match fixed_text(e) {
+                    Some(t) => prefix.push_str(&t),
+                    None => {
+                        prefix.push_str(&text_prefix(e));
+                        break;
+                    }
+                }
+            }
+            prefix
+        }
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => text_prefix(expr),
+        _ => String::new(),
+    }
+}
+
+/// the expression never fails
+fn always_matches(expr: &Expression) -> bool {
+    match expr {
+        Expression::Simple(Atom::Literal(l)) => l.is_empty(),
+        Expression::Repeat(rep) => rep.min.0 == 0,
+        Expression::And(mexpr)
+        | Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().all(always_matches)
+        }
+        Expression::Or(mexpr) => mexpr.0.iter().any(always_matches),
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => always_matches(expr),
+        _ => false,
+    }
+}
@@ -0,0 +1,178 @@
+#![warn(missing_docs)]
+//! Static checks over a set of rules
+//!
+//! They run after generating the rules, before parsing anything
+
+use crate::parser::expression::{Expression, MetaExpr, NamedExpr, SetOfRules, Transf2Expr};
+
+pub(crate) mod left_rec;
+pub(crate) mod lint;
+pub(crate) mod nullable;
+pub(crate) mod templates;
+
+#[cfg(test)]
+mod test;
+
+// -------------------------------------------------------------------------------------
+//  T Y P E S
+
+/// A problem found checking the grammar
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// rule where the problem was found (if any)
+    pub(crate) rule: Option<String>,
+    pub(crate) kind: IssueKind,
+}
+
+/// Kind of problems on a grammar
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// reference to a rule not defined
+    UndefinedRule(String),
+    /// there is no `main` rule to start parsing
+    MissingMain,
+    /// the rule is defined more than once
+    DuplicatedRule,
+    /// repetition without limit of an expression that can succeed
+    /// without consuming input  ie: `(a?)*`
+    NullableRepeat,
+    /// left recursive cycle (path of rules) where no rule can succeed
+    /// without the recursion, so it can't start growing
+    LeftRecursion(Vec<String>),
+    /// name used on a replace template, not available on its expression
+    UndefinedTemplateName(String),
+    /// the rule is not reachable from the start rule  (lint)
+    UnusedRule,
+    /// alternative (1 based) of an `or` that never matches because
+    /// a previous one matches first  ie: `'a' / 'ab'`  (lint)
+    UnreachableAlternative(usize),
+    /// left recursive cycle, not supported generating a specialized parser
+    SpecializedLeftRecursion(Vec<String>),
+}
+
+// -------------------------------------------------------------------------------------
+//  A P I
+
+impl Issue {
+    /// rule where the problem was found (if any)
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    /// kind of problem
+    pub fn kind(&self) -> &IssueKind {
+        &self.kind
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let rule = self.rule.as_deref().unwrap_or("");
+        match &self.kind {
+            IssueKind::UndefinedRule(r) => write!(f, "rule {}: undefined rule {}", rule, r),
+            IssueKind::MissingMain => write!(f, "missing rule main"),
+            IssueKind::DuplicatedRule => write!(f, "rule {}: defined more than once", rule),
+            IssueKind::NullableRepeat => write!(
+                f,
+                "rule {}: repeating an expression that can match empty (infinite loop)",
+                rule
+            ),
+            IssueKind::LeftRecursion(cycle) => write!(
+                f,
+                "rule {}: left recursion without alternative to start {} -> {}",
+                rule,
+                cycle.join(" -> "),
+                rule
+            ),
+            IssueKind::UndefinedTemplateName(n) => write!(
+                f,
+                "rule {}: name {} used on template is not on its expression",
+                rule, n
+            ),
+            IssueKind::UnusedRule => write!(f, "rule {}: not reachable from start rule", rule),
+            IssueKind::UnreachableAlternative(n) => write!(
+                f,
+                "rule {}: alternative {} never matches, a previous one matches first",
+                rule, n
+            ),
+            IssueKind::SpecializedLeftRecursion(cycle) => write!(
+                f,
+                "rule {}: left recursion not supported by specialized parser {} -> {}",
+                rule,
+                cycle.join(" -> "),
+                rule
+            ),
+        }
+    }
+}
+
+/// look for undefined rules, missing `main`, nullable repetitions,
+/// left recursions that can't start and undefined names on templates
+/// `duplicated` are the rules defined more than once, detected
+/// before building the set of rules
+pub(crate) fn check_rules(rules: &SetOfRules, duplicated: &[String]) -> Vec<Issue> {
+    let missing_main = if rules.0.contains_key("main") {
+        vec![]
+    } else {
+        vec![Issue {
+            rule: None,
+            kind: IssueKind::MissingMain,
+        }]
+    };
+
+    let mut names = rules.0.keys().collect::<Vec<_>>();
+    names.sort();
+    let undefined = names.into_iter().flat_map(|name| {
+        let mut refs = rule_refs(&rules.0[name].expr);
+        refs.sort();
+        refs.dedup();
+        refs.into_iter()
+            .filter(|r| !rules.0.contains_key(*r))
+            .map(move |r| Issue {
+                rule: Some(name.to_string()),
+                kind: IssueKind::UndefinedRule(r.to_string()),
+            })
+    });
+
+    let duplicated = duplicated.iter().map(|name| Issue {
+        rule: Some(name.to_string()),
+        kind: IssueKind::DuplicatedRule,
+    });
+
+    missing_main
+        .into_iter()
+        .chain(duplicated)
+        .chain(undefined)
+        .chain(nullable::check_nullable_repeats(rules))
+        .chain(left_rec::check_left_recursions(rules))
+        .chain(templates::check_template_names(rules))
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------
+//  S U P P O R T
+
+/// rules referenced from an expression (with repetitions)
+pub(crate) fn rule_refs(expr: &Expression) -> Vec<&str> {
+    match expr {
+        Expression::RuleName(name) => vec![name.as_str()],
+        _ => sub_expressions(expr)
+            .into_iter()
+            .flat_map(rule_refs)
+            .collect(),
+    }
+}
+
+/// direct sub expressions
+pub(crate) fn sub_expressions(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::Simple(_) | Expression::RuleName(_) => vec![],
+        Expression::And(mexpr) | Expression::Or(mexpr) => mexpr.0.iter().collect(),
+        Expression::Not(e) | Expression::Peek(e) => vec![e],
+        Expression::Repeat(rep) => vec![&rep.expression],
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => vec![expr],
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().collect()
+        }
+    }
+}
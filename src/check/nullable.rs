@@ -0,0 +1,78 @@
+//! Nullable analysis
+//!
+//! An expression is nullable if it can succeed without consuming input.
+//! Repeating without limit a nullable expression, would loop forever
+
+use super::{sub_expressions, Issue, IssueKind};
+use crate::parser::atom::Atom;
+use crate::parser::expression::{Expression, MetaExpr, NamedExpr, SetOfRules, Transf2Expr};
+use std::collections::HashSet;
+
+/// rules that can succeed without consuming input
+/// computed as a fixed point, starting with none of them nullable
+pub(crate) fn nullable_rules(rules: &SetOfRules) -> HashSet<&str> {
+    let mut nullables = HashSet::new();
+    loop {
+        let new_nullables = rules
+            .0
+            .iter()
+            .filter(|(name, ri)| {
+                !nullables.contains(name.as_str()) && is_nullable(&ri.expr, &nullables)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        if new_nullables.is_empty() {
+            return nullables;
+        }
+        nullables.extend(new_nullables);
+    }
+}
+
+/// the expression can succeed without consuming input
+pub(crate) fn is_nullable(expr: &Expression, nullables: &HashSet<&str>) -> bool {
+    match expr {
+        Expression::Simple(Atom::Literal(l)) => l.is_empty(),
+        Expression::Simple(Atom::Eof) => true,
+        Expression::Simple(Atom::Match(_))
+        | Expression::Simple(Atom::Dot)
+        | Expression::Simple(Atom::Expected(_)) => false,
+        Expression::And(mexpr) => mexpr.0.iter().all(|e| is_nullable(e, nullables)),
+        Expression::Or(mexpr) => mexpr.0.iter().any(|e| is_nullable(e, nullables)),
+        Expression::Not(_) | Expression::Peek(_) => true,
+        Expression::Repeat(rep) => rep.min.0 == 0 || is_nullable(&rep.expression, nullables),
+        Expression::RuleName(name) => nullables.contains(name.as_str()),
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { expr, .. })) => {
+            is_nullable(expr, nullables)
+        }
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().all(|e| is_nullable(e, nullables))
+        }
+    }
+}
+
+/// repetitions without max over a nullable expression
+pub(crate) fn check_nullable_repeats(rules: &SetOfRules) -> Vec<Issue> {
+    let nullables = nullable_rules(rules);
+
+    let mut names = rules.0.keys().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .filter(|name| has_nullable_repeat(&rules.0[*name].expr, &nullables))
+        .map(|name| Issue {
+            rule: Some(name.to_string()),
+            kind: IssueKind::NullableRepeat,
+        })
+        .collect()
+}
+
+fn has_nullable_repeat(expr: &Expression, nullables: &HashSet<&str>) -> bool {
+    match expr {
+        Expression::Repeat(rep) if rep.max.is_none() && is_nullable(&rep.expression, nullables) => {
+            true
+        }
+        _ => sub_expressions(expr)
+            .into_iter()
+            .any(|e| has_nullable_repeat(e, nullables)),
+    }
+}
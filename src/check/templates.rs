@@ -0,0 +1,93 @@
+//! Replace templates analysis
+//!
+//! Names used on a template have to be available on its expression
+
+use super::{Issue, IssueKind};
+use crate::parser::expression::{
+    Expression, MetaExpr, NamedExpr, ReplCond, ReplItem, ReplTemplate, SetOfRules, Transf2Expr,
+};
+use std::collections::HashSet;
+
+/// names used on templates not available on their expressions
+pub(crate) fn check_template_names(rules: &SetOfRules) -> Vec<Issue> {
+    let mut names = rules.0.keys().collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .flat_map(|name| {
+            let mut undefined = vec![];
+            undefined_template_names(&rules.0[name].expr, &mut undefined);
+            undefined.sort_unstable();
+            undefined.dedup();
+            undefined.into_iter().map(move |n| Issue {
+                rule: Some(name.to_string()),
+                kind: IssueKind::UndefinedTemplateName(n.to_string()),
+            })
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------
+//  S U P P O R T
+
+fn undefined_template_names<'a>(expr: &'a Expression, undefined: &mut Vec<&'a str>) {
+    if let Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr {
+        mexpr,
+        transf2_rules,
+    })) = expr
+    {
+        let mut available = HashSet::new();
+        mexpr
+            .0
+            .iter()
+            .for_each(|e| available_names(e, &mut available));
+        let mut used = vec![];
+        template_names(transf2_rules, &mut used);
+        undefined.extend(used.into_iter().filter(|n| !available.contains(n)));
+    }
+    super::sub_expressions(expr)
+        .into_iter()
+        .for_each(|e| undefined_template_names(e, undefined));
+}
+
+/// names (and rules) producing nodes for the template
+/// the nodes inside a name or other template are not visible
+fn available_names<'a>(expr: &'a Expression, available: &mut HashSet<&'a str>) {
+    match expr {
+        Expression::RuleName(name) => {
+            available.insert(name);
+        }
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { name, .. })) => {
+            available.insert(name);
+        }
+        Expression::MetaExpr(MetaExpr::Transf2(_))
+        | Expression::Not(_)
+        | Expression::Peek(_)
+        | Expression::Simple(_) => (),
+        Expression::And(mexpr) | Expression::Or(mexpr) => {
+            mexpr.0.iter().for_each(|e| available_names(e, available))
+        }
+        Expression::Repeat(rep) => available_names(&rep.expression, available),
+    }
+}
+
+fn template_names<'a>(template: &'a ReplTemplate, used: &mut Vec<&'a str>) {
+    for item in &template.0 {
+        match item {
+            ReplItem::Text(_) | ReplItem::ByPos(_) => (),
+            ReplItem::Function(f) => used.extend(crate::ast::replace::fn_name_arg(f)),
+            ReplItem::ByName(n)
+            | ReplItem::ByNameOpt(n)
+            | ReplItem::ByNameJoin(n, _)
+            | ReplItem::ByNameCount(n)
+            | ReplItem::ByNameIndex(n, _) => used.push(n),
+            ReplItem::If(cond, then, else_) => {
+                match cond {
+                    ReplCond::Named(n) | ReplCond::NamedEq(n, _) => used.push(n),
+                }
+                template_names(then, used);
+                template_names(else_, used);
+            }
+        }
+    }
+}
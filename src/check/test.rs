@@ -0,0 +1,184 @@
+//-----------------------------------------------------------------------
+//
+//  mod check  TEST
+//
+//-----------------------------------------------------------------------
+
+use super::{check_rules, Issue, IssueKind};
+
+#[test]
+fn test_check_ok() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: and![ref_rule!("a"), rep![ref_rule!("a"), 0]], descr: None},
+        "a" => RuleInfo{expr: lit!("a"), descr: None}
+    };
+
+    assert!(check_rules(&rules, &[]).is_empty());
+}
+
+#[test]
+fn test_check_undefined_and_main() {
+    let rules = rules! {
+        "start" => RuleInfo{expr: and![ref_rule!("a"), named!("n", ref_rule!("b")), ref_rule!("b")], descr: None},
+        "a" => RuleInfo{expr: or![lit!("a"), not!(ref_rule!("c"))], descr: None}
+    };
+
+    assert_eq!(
+        check_rules(&rules, &["a".to_string()]),
+        vec![
+            Issue {
+                rule: None,
+                kind: IssueKind::MissingMain
+            },
+            Issue {
+                rule: Some("a".to_string()),
+                kind: IssueKind::DuplicatedRule
+            },
+            Issue {
+                rule: Some("a".to_string()),
+                kind: IssueKind::UndefinedRule("c".to_string())
+            },
+            Issue {
+                rule: Some("start".to_string()),
+                kind: IssueKind::UndefinedRule("b".to_string())
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_check_nullable_repeat() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: rep![ref_rule!("a"), 0], descr: None},
+        "a" => RuleInfo{expr: or![lit!("a"), ref_rule!("b")], descr: None},
+        "b" => RuleInfo{expr: rep![lit!("b"), 0], descr: None}
+    };
+
+    assert_eq!(
+        check_rules(&rules, &[]),
+        vec![Issue {
+            rule: Some("main".to_string()),
+            kind: IssueKind::NullableRepeat
+        }]
+    );
+    assert_eq!(rules.nullable_rules(), vec!["a", "b", "main"]);
+}
+
+#[test]
+fn test_check_left_recursion() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: ref_rule!("a"), descr: None},
+        "a" => RuleInfo{expr: and![ref_rule!("b"), lit!("x")], descr: None},
+        "b" => RuleInfo{expr: and![rep![ref_rule!("a"), 0, 1], lit!("y")], descr: None},
+        "c" => RuleInfo{expr: and![rep![lit!("c"), 0], ref_rule!("d")], descr: None},
+        "d" => RuleInfo{expr: or![ref_rule!("c"), ref_rule!("b")], descr: None}
+    };
+
+    assert_eq!(
+        rules.left_recursions(),
+        vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["c".to_string(), "d".to_string()]
+        ]
+    );
+    assert!(check_rules(&rules, &[]).is_empty());
+}
+
+#[test]
+fn test_check_left_recursion_without_seed() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: or![and![ref_rule!("a"), lit!("x")], lit!("x")], descr: None},
+        "a" => RuleInfo{expr: and![named!("n", ref_rule!("main")), lit!("y")], descr: None},
+        "b" => RuleInfo{expr: and![not!(lit!("b")), ref_rule!("b")], descr: None}
+    };
+
+    assert_eq!(
+        check_rules(&rules, &[]),
+        vec![Issue {
+            rule: Some("b".to_string()),
+            kind: IssueKind::LeftRecursion(vec!["b".to_string()])
+        }]
+    );
+}
+
+#[test]
+fn test_lint() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: or![lit!("a"), and![lit!("a"), ref_rule!("b")], lit!("c")], descr: None},
+        "b" => RuleInfo{expr: or![rep![lit!("b"), 0], lit!("c")], descr: None},
+        "unused" => RuleInfo{expr: or![lit!("ab"), lit!("a"), ref_rule!("other")], descr: None},
+        "other" => RuleInfo{expr: lit!("o"), descr: None}
+    };
+
+    assert_eq!(
+        super::lint::lint(&rules, "main"),
+        vec![
+            Issue {
+                rule: Some("other".to_string()),
+                kind: IssueKind::UnusedRule
+            },
+            Issue {
+                rule: Some("unused".to_string()),
+                kind: IssueKind::UnusedRule
+            },
+            Issue {
+                rule: Some("b".to_string()),
+                kind: IssueKind::UnreachableAlternative(2)
+            },
+            Issue {
+                rule: Some("main".to_string()),
+                kind: IssueKind::UnreachableAlternative(2)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_check_template_names() {
+    let rules = rules! {
+        "main" => RuleInfo{ expr:transf2!( and!(named!("a", lit!("a")),
+                                                transf2!(and!(named!("b", lit!("b"))), t2rules!(t2_byname!("b"), t2_byname!("a"))),
+                                                rep!(ref_rule!("c"), 0),
+                                                not!(named!("d", lit!("d")))),
+                                           t2rules!(t2_byname!("a"), t2_byname!("b"), t2_byname!("c"), t2_byname_opt!("d")) ),
+                            descr:None },
+        "c" => RuleInfo{ expr:lit!("c"), descr:None }
+    };
+
+    assert_eq!(
+        check_rules(&rules, &[]),
+        vec![
+            Issue {
+                rule: Some("main".to_string()),
+                kind: IssueKind::UndefinedTemplateName("a".to_string())
+            },
+            Issue {
+                rule: Some("main".to_string()),
+                kind: IssueKind::UndefinedTemplateName("b".to_string())
+            },
+            Issue {
+                rule: Some("main".to_string()),
+                kind: IssueKind::UndefinedTemplateName("d".to_string())
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_check_template_names_on_functions() {
+    let rules = rules! {
+        "main" => RuleInfo{ expr:transf2!( and!(named!("name", lit!("a"))),
+                                           t2rules!(t2_funct!("upper name"), t2_funct!("len nmae"),
+                                                    t2_funct!("trim name other"), t2_funct!("custom missing"),
+                                                    t2_funct!("counter missing")) ),
+                            descr:None }
+    };
+
+    assert_eq!(
+        check_rules(&rules, &[]),
+        vec![Issue {
+            rule: Some("main".to_string()),
+            kind: IssueKind::UndefinedTemplateName("nmae".to_string())
+        }]
+    );
+}
@@ -0,0 +1,220 @@
+//! Compile `.peg` files from a `build.rs`
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Compile `.peg` files from a `build.rs`, writing the generated modules
+/// on `OUT_DIR`
+///
+/// ```no_run
+/// // build.rs  (main)
+/// if let Err(e) = yepp::Build::new().file("src/calc.peg").compile() {
+///     panic!("{}", e);
+/// }
+/// ```
+///
+/// And, on the crate...
+///
+/// ```ignore
+/// mod calc {
+///     include!(concat!(env!("OUT_DIR"), "/calc.rs"));
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Build {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    no_cargo_metadata: bool,
+}
+
+/// Errors compiling `.peg` files
+#[derive(Debug)]
+pub enum BuildError {
+    /// error reading or writing a file
+    Io(PathBuf, std::io::Error),
+    /// the grammar on the file is not valid
+    Grammar(PathBuf, crate::Error),
+    /// no `out_dir` configured and `OUT_DIR` is not defined (not running from `build.rs`)
+    MissingOutDir,
+    /// two `.peg` files would write the same module  (module, first file, second file)
+    DuplicatedModule(PathBuf, PathBuf, PathBuf),
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BuildError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            BuildError::Grammar(path, e) => write!(f, "{}: {}", path.display(), e),
+            BuildError::MissingOutDir => {
+                write!(
+                    f,
+                    "OUT_DIR not defined, configure out_dir or run from build.rs"
+                )
+            }
+            BuildError::DuplicatedModule(module, first, second) => write!(
+                f,
+                "{}: generated from {} and {}",
+                module.display(),
+                first.display(),
+                second.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl Build {
+    /// create a builder without files
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a `.peg` file. The module will be `<out_dir>/<file_stem>.rs`
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// add all the `.peg` files on a directory (recursively)
+    ///
+    /// The modules keep the relative path  ie: `<dir>/a/b.peg` -> `<out_dir>/a/b.rs`
+    pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.dirs.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// where to write the generated modules, by default `OUT_DIR`
+    pub fn out_dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// emit `cargo:rerun-if-changed` lines on stdout (default true)
+    pub fn cargo_metadata(mut self, emit: bool) -> Self {
+        self.no_cargo_metadata = !emit;
+        self
+    }
+
+    /// generate the modules, returning their paths
+    ///
+    /// A module is written just if its content changed.
+    /// Two files for the same module (same name, or a file also on a dir) are an error
+    pub fn compile(&self) -> Result<Vec<PathBuf>, BuildError> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or(BuildError::MissingOutDir)?,
+        };
+
+        let mut pegs = self
+            .files
+            .iter()
+            .map(|file| {
+                let module = PathBuf::from(file.file_name().unwrap_or_default());
+                (file.clone(), module)
+            })
+            .collect::<Vec<_>>();
+        for dir in &self.dirs {
+            self.rerun_if_changed(dir);
+            find_peg_files(dir, dir, &mut pegs)?;
+        }
+
+        let mut modules = HashMap::new();
+        for (peg, module) in &pegs {
+            let dest = out_dir.join(module).with_extension("rs");
+            if let Some(first) = modules.insert(dest.clone(), peg) {
+                return Err(BuildError::DuplicatedModule(
+                    dest,
+                    first.clone(),
+                    peg.clone(),
+                ));
+            }
+        }
+
+        pegs.iter()
+            .map(|(peg, module)| {
+                self.rerun_if_changed(peg);
+                let dest = out_dir.join(module).with_extension("rs");
+                gen_module(peg, &dest)?;
+                Ok(dest)
+            })
+            .collect()
+    }
+
+    fn rerun_if_changed(&self, path: &Path) {
+        if !self.no_cargo_metadata {
+            let _ = writeln!(
+                std::io::stdout(),
+                "cargo:rerun-if-changed={}",
+                path.display()
+            );
+        }
+    }
+}
+
+fn find_peg_files(
+    root: &Path,
+    dir: &Path,
+    pegs: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<(), BuildError> {
+    let io_err = |e| BuildError::Io(dir.to_path_buf(), e);
+
+    let mut entries = fs::read_dir(dir)
+        .map_err(io_err)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_err)?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_peg_files(root, &path, pegs)?;
+        } else if path.extension() == Some(OsStr::new("peg")) {
+            let module = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            pegs.push((path, module));
+        }
+    }
+    Ok(())
+}
+
+fn gen_module(peg: &Path, dest: &Path) -> Result<(), BuildError> {
+    let txt_peg = fs::read_to_string(peg).map_err(|e| BuildError::Io(peg.to_path_buf(), e))?;
+    let rules = crate::Peg::new(&txt_peg)
+        .gen_rules()
+        .map_err(|e| BuildError::Grammar(peg.to_path_buf(), e))?;
+
+    let code = module_code(peg, &rules);
+    if fs::read_to_string(dest).ok().as_deref() == Some(code.as_str()) {
+        return Ok(());
+    }
+
+    let io_err = |e| BuildError::Io(dest.to_path_buf(), e);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    fs::write(dest, code).map_err(io_err)
+}
+
+fn module_code(peg: &Path, rules: &crate::SetOfRules) -> String {
+    format!(
+        "// generated by yepp from {:?}, don't edit
+
+{}
+/// rules generated from the grammar
+pub fn rules() -> yepp::SetOfRules {{
+rules!(
+{}
+)
+}}
+",
+        peg,
+        crate::gcode::USE_MACROS,
+        crate::gcode::rust_from_rules(rules)
+    )
+}
@@ -1,6 +1,9 @@
 #![warn(missing_docs)]
 
+pub(crate) mod build;
 pub(crate) mod proc_peg_files;
+pub(crate) mod specialized;
+pub(crate) mod typed;
 
 use crate::parser::{
     atom,
@@ -9,124 +12,202 @@ use crate::parser::{
 };
 use idata::IString;
 
+/// `use` of the macros for generated code, to compile out of this crate
+pub(crate) const USE_MACROS: &str = "#[allow(unused_imports)]
+use yepp::{
+    and, dot, ematch, eof, expected, lit, named, not, or, peek, ref_rule, rep, rules, t2_byname,
+    t2_byname_count, t2_byname_index, t2_byname_join, t2_byname_opt, t2_bypos, t2_funct,
+    t2_if_eq, t2_if_named, t2_text, t2rules, transf2,
+};
+#[allow(unused_imports)]
+use yepp::grammar::NameIndex;
+";
+
+/// How the literals of the peg are written on the generated code
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Literals {
+    /// the same text the interpreted rules match (`\n` is backslash and n)
+    Exact,
+    /// escapes written on the peg (`\n`...) are kept, and interpreted by rust
+    ///
+    /// It's the way the peg grammar parser (`rules_for_peg.rs`) is bootstrapped
+    RustEscapes,
+}
+
 /// Generate a string with rust code from a ```expression::SetOfRules```
 pub(crate) fn rust_from_rules(rules: &expression::SetOfRules) -> String {
+    rust_from_rules_lits(rules, Literals::Exact)
+}
+
+/// Generate rust code choosing how to write the literals
+pub(crate) fn rust_from_rules_lits(rules: &expression::SetOfRules, lits: Literals) -> String {
     let add_rule = |crules: String, rule: &str| -> String {
         let begin = if crules.is_empty() { "  " } else { ", " };
         crules + "\n       " + begin + rule
     };
 
     rules.0.iter().fold("".to_string(), |acc, (name, ri)| {
-        add_rule(acc, &rule2code(name, ri))
+        add_rule(acc, &rule2code(name, ri, lits))
     })
 }
 
-fn rule2code(name: &str, ri: &RuleInfo) -> String {
+fn rule2code(name: &str, ri: &RuleInfo, lits: Literals) -> String {
     format!(
         r##"r#"{}"# => RuleInfo{{ expr:{}, descr:{} }}"##,
         name,
-        expr2code(&ri.expr),
+        expr2code(&ri.expr, lits),
         match &ri.descr {
-            Some(d) => format!("Some({})", d),
+            Some(d) => format!("Some({:?}.to_owned())", d),
             None => "None".to_owned(),
         }
     )
 }
 
-fn expr2code(expr: &Expression) -> String {
+fn expr2code(expr: &Expression, lits: Literals) -> String {
     match expr {
-        Expression::Simple(atom) => atom2code(atom),
-        Expression::And(mexpr) => format!("and!({})", mexpr2code(mexpr)),
-        Expression::Or(mexpr) => format!("or!({})", mexpr2code(mexpr)),
-        Expression::Not(e) => format!("not!({})", expr2code(e)),
-        Expression::Peek(e) => format!("peek!({})", expr2code(e)),
-        Expression::Repeat(rep) => repeat2code(rep),
+        Expression::Simple(atom) => atom2code(atom, lits),
+        Expression::And(mexpr) => format!("and!({})", mexpr2code(mexpr, lits)),
+        Expression::Or(mexpr) => format!("or!({})", mexpr2code(mexpr, lits)),
+        Expression::Not(e) => format!("not!({})", expr2code(e, lits)),
+        Expression::Peek(e) => format!("peek!({})", expr2code(e, lits)),
+        Expression::Repeat(rep) => repeat2code(rep, lits),
         Expression::RuleName(rname) => format!(r##"ref_rule!(r#"{}"#)"##, rname),
-        Expression::MetaExpr(me) => metaexpr2code(me),
+        Expression::MetaExpr(me) => metaexpr2code(me, lits),
     }
 }
 
-fn metaexpr2code(me: &MetaExpr) -> String {
+fn metaexpr2code(me: &MetaExpr, lits: Literals) -> String {
     use crate::parser::expression::MetaExpr::{Named, Transf2};
     use crate::parser::expression::{NamedExpr, Transf2Expr};
     match me {
-        Named(NamedExpr { name, expr }) => format!("named!(\"{}\", {})", name, expr2code(expr)),
+        Named(NamedExpr { name, expr }) => {
+            format!("named!(\"{}\", {})", name, expr2code(expr, lits))
+        }
         Transf2(Transf2Expr {
             mexpr,
             transf2_rules,
-        }) => transf2code(mexpr, transf2_rules),
+        }) => transf2code(mexpr, transf2_rules, lits),
     }
 }
 
-fn transf2code(expr: &MultiExpr, t2: &ReplTemplate) -> String {
+fn transf2code(expr: &MultiExpr, t2: &ReplTemplate, lits: Literals) -> String {
     format!(
         "transf2!( and!( {} ) , t2rules!({}) )",
-        mexpr2code(expr),
+        mexpr2code(expr, lits),
         transf2templ2code(t2)
     )
 }
 
-fn transf2templ2code(t: &ReplTemplate) -> String {
-    use crate::parser::expression::ReplItem;
+pub(crate) fn transf2templ2code(t: &ReplTemplate) -> String {
+    use crate::parser::expression::{ReplCond, ReplItem};
     t.0.iter().fold("".to_string(), |acc, i| {
         let code = match i {
-            ReplItem::Text(t) => format!(r#"t2_text!("{}"), "#, t),
-            ReplItem::ByPos(p) => format!(r#"t2_bypos!("{}"), "#, p),
+            ReplItem::Text(t) => format!(r#"t2_text!({:?}), "#, t),
+            ReplItem::ByPos(p) => format!(r#"t2_bypos!({}), "#, p),
             ReplItem::ByName(p) => format!(r#"t2_byname!("{}"), "#, p),
             ReplItem::ByNameOpt(p) => format!(r#"t2_byname_opt!("{}"), "#, p),
             ReplItem::Function(p) => format!(r#"t2_funct!("{}"), "#, p),
+            ReplItem::ByNameJoin(p, sep) => format!(r#"t2_byname_join!("{}", {:?}), "#, p, sep),
+            ReplItem::ByNameCount(p) => format!(r#"t2_byname_count!("{}"), "#, p),
+            ReplItem::If(ReplCond::Named(p), then, else_) => format!(
+                r#"t2_if_named!("{}", t2rules!({}), t2rules!({})), "#,
+                p,
+                transf2templ2code(then),
+                transf2templ2code(else_)
+            ),
+            ReplItem::If(ReplCond::NamedEq(p, lit), then, else_) => format!(
+                r#"t2_if_eq!("{}", {:?}, t2rules!({}), t2rules!({})), "#,
+                p,
+                lit,
+                transf2templ2code(then),
+                transf2templ2code(else_)
+            ),
+            ReplItem::ByNameIndex(p, idx) => {
+                format!(r#"t2_byname_index!("{}", NameIndex::{:?}), "#, p, idx)
+            }
         };
         acc.iappend(&code)
     })
 }
 
-fn mexpr2code(mexpr: &expression::MultiExpr) -> String {
+fn mexpr2code(mexpr: &expression::MultiExpr, lits: Literals) -> String {
     mexpr
         .0
         .iter()
         .fold(String::new(), |acc, expr| match acc.len() {
-            0 => expr2code(expr),
-            _ => format!("{}, {}", acc, expr2code(expr)),
+            0 => expr2code(expr, lits),
+            _ => format!("{}, {}", acc, expr2code(expr, lits)),
         })
 }
 
-fn atom2code(atom: &Atom) -> String {
-    let replace_esc = |s: String| {
-        s.replace("\n", r#"\n"#)
-            .replace("\r", r#"\r"#)
-            .replace("\t", r#"\t"#)
-            .replace(r#"""#, r#"\""#)
-    };
+/// content of a rust string literal for a peg literal
+///
+/// Escapes written on the peg (`\n`...) are kept, and interpreted by rust
+pub(crate) fn replace_esc(s: &str) -> String {
+    s.replace("\n", r#"\n"#)
+        .replace("\r", r#"\r"#)
+        .replace("\t", r#"\t"#)
+        .replace(r#"""#, r#"\""#)
+}
 
+/// rust string literal for a peg literal
+pub(crate) fn str_lit(s: &str, lits: Literals) -> String {
+    match lits {
+        Literals::Exact => format!("{:?}", s),
+        Literals::RustEscapes => format!(r#""{}""#, replace_esc(s)),
+    }
+}
+
+fn atom2code(atom: &Atom, lits: Literals) -> String {
     match atom {
-        Atom::Literal(s) => format!(r#"lit!("{}")"#, replace_esc(s.to_string())),
-        Atom::Expected(s) => format!(r#"expected!("{}")"#, replace_esc(s.to_string())),
-        Atom::Match(mrules) => match_rules2code(mrules),
+        Atom::Literal(s) => format!("lit!({})", str_lit(s, lits)),
+        Atom::Expected(s) => format!("expected!({})", str_lit(s, lits)),
+        Atom::Match(mrules) => match_rules2code(mrules, lits),
         Atom::Dot => "dot!()".to_string(),
         Atom::Eof => "eof!()".to_string(),
     }
 }
 
-fn match_rules2code(mrules: &atom::MatchRules) -> String {
-    fn bounds2code(acc: String, bounds: &[(char, char)]) -> String {
-        match bounds.split_first() {
-            Some(((f, t), rest)) => {
-                format!(", from '{}', to '{}' {}", f, t, bounds2code(acc, rest))
+fn match_rules2code(mrules: &atom::MatchRules, lits: Literals) -> String {
+    fn bounds2code(acc: String, bounds: &[(char, char)], lits: Literals) -> String {
+        match (bounds.split_first(), lits) {
+            (Some(((f, t), rest)), Literals::Exact) => {
+                format!(
+                    ", from {:?}, to {:?} {}",
+                    f,
+                    t,
+                    bounds2code(acc, rest, lits)
+                )
+            }
+            (Some(((f, t), rest)), Literals::RustEscapes) => {
+                format!(
+                    ", from '{}', to '{}' {}",
+                    f,
+                    t,
+                    bounds2code(acc, rest, lits)
+                )
             }
-            None => acc,
+            (None, _) => acc,
         }
     }
 
-    format!(
-        r##"ematch!(chlist r#"{}"#  {})"##,
-        &mrules.0,
-        bounds2code(String::new(), &mrules.1)
-    )
+    let chlist = match lits {
+        Literals::Exact => format!("{:?}", &mrules.0),
+        Literals::RustEscapes => format!(r##"r#"{}"#"##, &mrules.0),
+    };
+    match mrules.1.is_empty() {
+        true => format!("ematch!(chlist {}, from2 vec![])", chlist),
+        false => format!(
+            "ematch!(chlist {}  {})",
+            chlist,
+            bounds2code(String::new(), &mrules.1, lits)
+        ),
+    }
 }
 
-fn repeat2code(rep: &expression::RepInfo) -> String {
+fn repeat2code(rep: &expression::RepInfo, lits: Literals) -> String {
     "rep!(".to_owned()
-        + &expr2code(&rep.expression)
+        + &expr2code(&rep.expression, lits)
         + ", "
         + &rep.min.0.to_string()
         + &match rep.max {
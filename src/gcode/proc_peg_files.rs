@@ -3,92 +3,84 @@ use std::ffi::OsStr;
 use std::fs;
 use std::path::Path;
 
-pub(crate) fn run(dir: &Path) {
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> crate::Error + '_ {
+    move |e| crate::Error::IoErr(path.to_path_buf(), e)
+}
+
+pub(crate) fn run(dir: &Path) -> Result<(), crate::Error> {
     _run(dir, false)
 }
 
-pub(crate) fn run_force(dir: &Path) {
+pub(crate) fn run_force(dir: &Path) -> Result<(), crate::Error> {
     _run(dir, true)
 }
 
-pub(crate) fn _run(dir: &Path, force: bool) {
-    println!("running: {:?}", dir);
-    for entry in fs::read_dir(dir).expect("cannot read directory") {
-        let entry = entry.expect("cannot read file");
+pub(crate) fn _run(dir: &Path, force: bool) -> Result<(), crate::Error> {
+    for entry in fs::read_dir(dir).map_err(io_err(dir))? {
+        let entry = entry.map_err(io_err(dir))?;
         let path = entry.path();
         if path.is_dir() {
-            run(&path);
+            _run(&path, force)?;
         } else if path.is_file() && path.extension() == Some(OsStr::new("peg")) {
             let orig_file = &path;
             let dest_file = &path.with_extension("rs");
 
-            if force || require_generation(orig_file, dest_file) {
-                gen_file(&orig_file, &dest_file);
+            if force || require_generation(orig_file, dest_file)? {
+                gen_file(orig_file, dest_file)?;
             }
         }
     }
+    Ok(())
 }
 
-fn get_rust_rules2parse_peg2(txt_peg: &str) -> String {
-    use crate::ir::Ir;
-
-    let irtxt = crate::rules_for_peg::rules()
-        .parse(txt_peg)
-        .unwrap()
-        .replace(None)
-        .unwrap();
-    let ir = Ir::new(&irtxt.str());
-
-    let rules = ir.get_rules().unwrap();
+fn get_rust_rules2parse_peg2(txt_peg: &str) -> Result<String, crate::Error> {
+    let rules = crate::Peg::new(txt_peg).gen_rules()?;
 
-    crate::gcode::rust_from_rules(&rules)
+    Ok(crate::gcode::rust_from_rules_lits(
+        &rules,
+        crate::gcode::Literals::RustEscapes,
+    ))
 }
 
-fn require_generation(origin: &Path, destiny: &Path) -> bool {
-    let created_origin = fs::metadata(origin)
-        .unwrap_or_else(|_| panic!("error getting metadata from file {:?}", origin))
-        .modified()
-        .unwrap_or_else(|_| panic!("cannot read date from file {:?}", origin));
-    let meta_dest = fs::metadata(destiny);
+fn require_generation(origin: &Path, destiny: &Path) -> Result<bool, crate::Error> {
+    let modified = |path| {
+        fs::metadata(path)
+            .and_then(|md| md.modified())
+            .map_err(io_err(path))
+    };
 
-    if let Ok(md) = meta_dest {
-        created_origin
-            > md.modified()
-                .unwrap_or_else(|_| panic!("cannot read date from file {:?}", md))
+    if destiny.exists() {
+        Ok(modified(origin)? > modified(destiny)?)
     } else {
-        true
+        Ok(true)
     }
 }
 
-fn gen_file(origin: &Path, destiny: &Path) {
-    println!("init generate file {:?}", origin);
-    let txt_peg =
-        fs::read_to_string(&origin).unwrap_or_else(|_| panic!("failed to read input {:?}", origin));
+fn gen_file(origin: &Path, destiny: &Path) -> Result<(), crate::Error> {
+    let txt_peg = fs::read_to_string(origin).map_err(io_err(origin))?;
 
-    let rust_rules = get_rust_rules2parse_peg2(&txt_peg);
+    let rust_rules = get_rust_rules2parse_peg2(&txt_peg)?;
 
-    let _ = fs::rename(&destiny, destiny.with_extension("rs.backup"));
+    let _ = fs::rename(destiny, destiny.with_extension("rs.backup"));
 
     fs::write(
-        &destiny,
+        destiny,
         format!(
             "
 #![warn(missing_docs)]
 //! Module to deal with rules (aka SetOfRules)
 //!
 
-use crate::parser;
-
-pub(crate) fn rules() -> parser::expression::SetOfRules {{
+{}
+pub(crate) fn rules() -> yepp::SetOfRules {{
 rules!(
 {}
 )
 }}
 ",
+            crate::gcode::USE_MACROS,
             rust_rules
         ),
     )
-    .expect("failed to write result");
-
-    println!("end generated {:?}", origin);
+    .map_err(io_err(destiny))
 }
@@ -0,0 +1,281 @@
+//! Generate a recursive descent parser, one function per rule
+//!
+//! The generated code uses `yepp::specialized` to produce the same
+//! `ast` and errors than the interpreter
+
+use crate::parser::{
+    atom::{Atom, MatchRules},
+    expression::{
+        Expression, MetaExpr, MultiExpr, NamedExpr, RepInfo, RuleInfo, SetOfRules, Transf2Expr,
+    },
+};
+
+/// Generate the rust code of a specialized parser for `rules`
+///
+/// The rules can't have left recursion, nor references to undefined rules
+pub(crate) fn rust_specialized(rules: &SetOfRules) -> String {
+    let rule_fns = rules
+        .0
+        .iter()
+        .map(|(name, ri)| rule2code(name, ri))
+        .collect::<String>();
+    let start_rules = rules
+        .0
+        .keys()
+        .map(|name| format!("\n        {:?} => {}(st),", name, fn_name(name)))
+        .collect::<String>();
+
+    format!(
+        "{}
+use yepp::specialized::{{self, State}};
+
+/// parse `text` starting on rule `main`, it has to be consumed completely
+pub fn parse(text: &str) -> Result<yepp::Node, Box<yepp::ParserError>> {{
+    parse_from(\"main\", text)
+}}
+
+/// parse `text` starting on `rule`, it has to be consumed completely
+pub fn parse_from(rule: &str, text: &str) -> Result<yepp::Node, Box<yepp::ParserError>> {{
+    let st = State::new(text);
+    specialized::finish(match rule {{{}
+        _ => specialized::missing_rule(st, rule),
+    }})
+}}
+{}",
+        crate::gcode::USE_MACROS,
+        start_rules,
+        rule_fns
+    )
+}
+
+/// name of the function for a rule
+///
+/// Chars not valid on identifiers are written as `_u<hex>_`
+fn fn_name(rule: &str) -> String {
+    rule.chars().fold("r_".to_string(), |mut acc, ch| {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            acc.push(ch);
+        } else {
+            acc.push_str(&format!("_u{:x}_", ch as u32));
+        }
+        acc
+    })
+}
+
+/// functions for subexpressions, declared inside the rule function
+struct Helpers(Vec<String>);
+
+impl Helpers {
+    fn add(&mut self, body: String) -> String {
+        let name = format!("e{}", self.0.len() + 1);
+        self.0.push(format!(
+            "
+    fn {}(st: State) -> specialized::Result {{{}
+    }}
+",
+            name, body
+        ));
+        name
+    }
+}
+
+fn rule2code(name: &str, ri: &RuleInfo) -> String {
+    let mut helpers = Helpers(vec![]);
+    let code = expr2code(&ri.expr, "st", &mut helpers);
+
+    format!(
+        "
+/// rule `{}`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn {}(st: State) -> specialized::Result {{{}
+    specialized::rule({:?}, {:?}, st.pos().clone(), {})
+}}
+",
+        name,
+        fn_name(name),
+        helpers.0.concat(),
+        name,
+        ri.descr,
+        code
+    )
+}
+
+//  code of an expression parsing from `input` (`st` or `st.clone()`)
+//  giving a `specialized::Result`
+fn expr2code(expr: &Expression, input: &str, helpers: &mut Helpers) -> String {
+    match expr {
+        Expression::Simple(atom) => atom2code(atom, input),
+        Expression::And(mexpr) => and2code(mexpr, input, helpers),
+        Expression::Or(mexpr) => or2code(mexpr, input, helpers),
+        Expression::Not(e) => format!(
+            "specialized::not(st.clone(), {})",
+            expr2code(e, input, helpers)
+        ),
+        Expression::Peek(e) => format!(
+            "specialized::peek(st.clone(), {})",
+            expr2code(e, input, helpers)
+        ),
+        Expression::Repeat(rep) => repeat2code(rep, input, helpers),
+        Expression::RuleName(rule) => format!("{}({})", fn_name(rule), input),
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { name, expr })) => format!(
+            "specialized::named({:?}, st.pos().clone(), {})",
+            name,
+            expr2code(expr, input, helpers)
+        ),
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr {
+            mexpr,
+            transf2_rules,
+        })) => format!(
+            "specialized::transf2(st.pos().clone(), {}, || t2rules!({}))",
+            and2code(mexpr, input, helpers),
+            crate::gcode::transf2templ2code(transf2_rules)
+        ),
+    }
+}
+
+fn atom2code(atom: &Atom, input: &str) -> String {
+    match atom {
+        Atom::Literal(s) => format!("specialized::lit({}, {:?})", input, s),
+        Atom::Expected(s) => format!("specialized::expected({}, {:?})", input, s),
+        Atom::Match(mrules) => format!(
+            "specialized::char_match({}, {}, {:?})",
+            input,
+            match2code(mrules),
+            format!("match {} {:?}", mrules.0, mrules.1)
+        ),
+        Atom::Dot => format!("specialized::dot({})", input),
+        Atom::Eof => format!("specialized::eof({})", input),
+    }
+}
+
+fn match2code(mrules: &MatchRules) -> String {
+    let patterns = mrules
+        .0
+        .chars()
+        .map(|ch| format!("{:?}", ch))
+        .chain(
+            mrules
+                .1
+                .iter()
+                .map(|(from, to)| format!("{:?}..={:?}", from, to)),
+        )
+        .collect::<Vec<_>>();
+
+    if patterns.is_empty() {
+        "|_| false".to_string()
+    } else {
+        format!("|ch| matches!(ch, {})", patterns.join(" | "))
+    }
+}
+
+//  a sequence of one is the expression
+fn and2code(mexpr: &MultiExpr, input: &str, helpers: &mut Helpers) -> String {
+    match mexpr.0.as_slice() {
+        [] => format!("Ok(({}, vec![]))", input),
+        [expr] => expr2code(expr, input, helpers),
+        exprs => {
+            let steps = exprs
+                .iter()
+                .map(|expr| {
+                    format!(
+                        "
+        let (st, n) = {}?;
+        nodes.extend(n);",
+                        expr2code(expr, "st", helpers)
+                    )
+                })
+                .collect::<String>();
+            let name = helpers.add(format!(
+                "
+        let mut nodes = vec![];{}
+        Ok((st, nodes))",
+                steps
+            ));
+            format!("{}({})", name, input)
+        }
+    }
+}
+
+//  an `or` of one gives the same result and error than the expression
+fn or2code(mexpr: &MultiExpr, input: &str, helpers: &mut Helpers) -> String {
+    match mexpr.0.as_slice() {
+        [expr] => expr2code(expr, input, helpers),
+        exprs => {
+            let alternatives = exprs
+                .iter()
+                .map(|expr| {
+                    format!(
+                        "
+        let err = match {} {{
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        }};",
+                        expr2code(expr, "st.clone()", helpers)
+                    )
+                })
+                .collect::<String>();
+            let name = helpers.add(format!(
+                "
+        let err = None;{}
+        Err(specialized::or_end(&st, err))",
+                alternatives
+            ));
+            format!("{}({})", name, input)
+        }
+    }
+}
+
+fn repeat2code(rep: &RepInfo, input: &str, helpers: &mut Helpers) -> String {
+    let min = rep.min.0;
+    let max = rep.max.as_ref().map(|m| m.0);
+    let counting = min > 0 || max.is_some();
+
+    let err_arms = if min > 0 {
+        format!(
+            "
+                Err(e) if count >= {} => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),",
+            min
+        )
+    } else {
+        "
+                Err(e) => return specialized::repeat_end(st, nodes, e),"
+            .to_string()
+    };
+    let touch_max = match max {
+        Some(max) => format!("count + 1 == {} || ", max),
+        None => String::new(),
+    };
+
+    let inner = expr2code(&rep.expression, "st.clone()", helpers);
+    let name = helpers.add(format!(
+        "
+        let mut st = st;
+        let mut nodes = vec![];{}
+        loop {{
+            match {} {{{}
+                Ok((next, n)) => {{
+                    nodes.extend(n);
+                    if {}specialized::same_pos(&st, &next) {{
+                        return Ok((next, nodes));
+                    }}
+                    st = next;{}
+                }}
+            }}
+        }}",
+        if counting {
+            "\n        let mut count = 0;"
+        } else {
+            ""
+        },
+        inner,
+        err_arms,
+        touch_max,
+        if counting {
+            "\n                    count += 1;"
+        } else {
+            ""
+        },
+    ));
+    format!("{}({})", name, input)
+}
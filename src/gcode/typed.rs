@@ -0,0 +1,454 @@
+//! Generate typed `ast` structs and enums, one per rule, with a
+//! `TryFrom<&yepp::Node>` conversion
+//!
+//! * named captures and references to rules become fields
+//! * a rule with alternatives (`or`) becomes an enum, with a variant per alternative
+//! * repetitions become `Vec`, optionals (`?` or inside an `or`) become `Option`
+//! * a rule without fields is a tuple struct with the parsed text
+
+use crate::ast::functions::camel_case;
+use crate::parser::{
+    atom::Atom,
+    expression::{Expression, MetaExpr, MultiExpr, NamedExpr, RuleInfo, SetOfRules, Transf2Expr},
+};
+use std::collections::{HashMap, HashSet};
+
+/// names that would hide a type or constructor used on generated code
+const RESERVED_TYPES: &[&str] = &[
+    "Box",
+    "Clone",
+    "Debug",
+    "Err",
+    "Error",
+    "None",
+    "Ok",
+    "Option",
+    "PartialEq",
+    "Result",
+    "Self",
+    "Some",
+    "String",
+    "TryFrom",
+    "Vec",
+];
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// Generate the rust code of the typed `ast` for `rules`
+///
+/// The rules can't have references to undefined rules
+pub(crate) fn rust_typed(rules: &SetOfRules) -> String {
+    let types = type_names(rules);
+    let items = rules
+        .0
+        .iter()
+        .map(|(name, ri)| rule2code(name, ri, &types))
+        .collect::<String>();
+
+    format!(
+        "#[allow(unused_imports)]
+use std::convert::TryFrom;
+use yepp::typed;
+{}",
+        items
+    )
+}
+
+//-----------------------------------------------------------------------
+//  NAMES
+
+fn type_names(rules: &SetOfRules) -> HashMap<&str, String> {
+    let mut used = HashSet::new();
+    rules
+        .0
+        .keys()
+        .map(|rule| {
+            let camel = camel_case(rule);
+            let mut chars = camel.chars();
+            let pascal = match chars.next() {
+                Some(first) if first.is_ascii_alphabetic() => {
+                    first.to_uppercase().chain(chars).collect::<String>()
+                }
+                _ => format!("Rule{}", camel),
+            };
+            let pascal = if RESERVED_TYPES.contains(&pascal.as_str()) {
+                pascal + "Rule"
+            } else {
+                pascal
+            };
+            (rule.as_str(), unique(&mut used, pascal))
+        })
+        .collect()
+}
+
+//  adding a number if it's already used
+fn unique(used: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut i = 1;
+    while used.contains(&candidate) {
+        i += 1;
+        candidate = format!("{}{}", name, i);
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+//  chars not valid on identifiers are written as `_u<hex>_`
+fn field_ident(name: &str) -> String {
+    let ident = name.chars().fold(String::new(), |mut acc, ch| {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            acc.push(ch);
+        } else {
+            acc.push_str(&format!("_u{:x}_", ch as u32));
+        }
+        acc
+    });
+    match ident.as_str() {
+        "_" | "self" | "super" | "crate" | "Self" => format!("{}_", ident),
+        kw if KEYWORDS.contains(&kw) => format!("r#{}", kw),
+        id if id.starts_with(|ch: char| ch.is_ascii_digit()) => format!("_{}", id),
+        _ => ident,
+    }
+}
+
+//-----------------------------------------------------------------------
+//  FIELDS
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mult {
+    One,
+    Opt,
+    Many,
+}
+
+#[derive(PartialEq)]
+enum Source {
+    Named(String),
+    Rule(String),
+}
+
+struct Field {
+    source: Source,
+    //  the rule, when the field is a rule node
+    rule: Option<String>,
+    mult: Mult,
+}
+
+//  single element `and`, `or` and transformations are the inner expression
+fn unwrap(expr: &Expression) -> &Expression {
+    match expr {
+        Expression::And(MultiExpr(exprs)) | Expression::Or(MultiExpr(exprs))
+            if exprs.len() == 1 =>
+        {
+            unwrap(&exprs[0])
+        }
+        Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. }))
+            if mexpr.0.len() == 1 =>
+        {
+            unwrap(&mexpr.0[0])
+        }
+        _ => expr,
+    }
+}
+
+fn fields(expr: &Expression) -> Vec<Field> {
+    let mut fields = vec![];
+    add_fields(expr, Mult::One, &mut fields);
+    fields
+}
+
+fn add_fields(expr: &Expression, mult: Mult, fields: &mut Vec<Field>) {
+    let optional = match mult {
+        Mult::Many => Mult::Many,
+        _ => Mult::Opt,
+    };
+    match expr {
+        Expression::Simple(_) | Expression::Not(_) | Expression::Peek(_) => {}
+        Expression::And(mexpr)
+        | Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr { mexpr, .. })) => {
+            mexpr.0.iter().for_each(|e| add_fields(e, mult, fields))
+        }
+        Expression::Or(mexpr) if mexpr.0.len() == 1 => add_fields(&mexpr.0[0], mult, fields),
+        Expression::Or(mexpr) => mexpr.0.iter().for_each(|e| add_fields(e, optional, fields)),
+        Expression::Repeat(rep) => {
+            let mult = match rep.max.as_ref().map(|m| m.0) {
+                Some(1) if rep.min.0 == 0 => optional,
+                Some(1) => mult,
+                _ => Mult::Many,
+            };
+            add_fields(&rep.expression, mult, fields)
+        }
+        Expression::RuleName(rule) => {
+            add_field(fields, Source::Rule(rule.clone()), Some(rule.clone()), mult)
+        }
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { name, expr })) => {
+            let rule = match unwrap(expr) {
+                Expression::RuleName(rule) => Some(rule.clone()),
+                _ => None,
+            };
+            add_field(fields, Source::Named(name.clone()), rule, mult)
+        }
+    }
+}
+
+//  a field found again is a `Vec`, and text if the rule is different
+fn add_field(fields: &mut Vec<Field>, source: Source, rule: Option<String>, mult: Mult) {
+    match fields.iter_mut().find(|f| f.source == source) {
+        Some(field) => {
+            field.mult = Mult::Many;
+            if field.rule != rule {
+                field.rule = None;
+            }
+        }
+        None => fields.push(Field { source, rule, mult }),
+    }
+}
+
+fn field_idents(fields: &[Field]) -> Vec<String> {
+    let mut used = HashSet::new();
+    fields
+        .iter()
+        .map(|f| match &f.source {
+            Source::Named(name) | Source::Rule(name) => unique(&mut used, field_ident(name)),
+        })
+        .collect()
+}
+
+fn field_type(field: &Field, types: &HashMap<&str, String>) -> String {
+    match (&field.rule, field.mult) {
+        (Some(rule), Mult::One) => format!("Box<{}>", types[rule.as_str()]),
+        (Some(rule), Mult::Opt) => format!("Option<Box<{}>>", types[rule.as_str()]),
+        (Some(rule), Mult::Many) => format!("Vec<{}>", types[rule.as_str()]),
+        (None, Mult::One) => "String".to_string(),
+        (None, Mult::Opt) => "Option<String>".to_string(),
+        (None, Mult::Many) => "Vec<String>".to_string(),
+    }
+}
+
+//  conversion from `nodes` (children of `node`)
+fn field_conversion(field: &Field) -> String {
+    let (found, descr) = match &field.source {
+        Source::Named(name) => (
+            format!("typed::named(&nodes, {:?})", name),
+            format!("named {}", name),
+        ),
+        Source::Rule(rule) => (
+            format!("typed::rules(&nodes, {:?})", rule),
+            format!("rule {}", rule),
+        ),
+    };
+    match (&field.rule, field.mult) {
+        (Some(_), Mult::One) => format!(
+            "Box::new(typed::value(typed::one(node, {}, {:?})?)?)",
+            found, descr
+        ),
+        (Some(_), Mult::Opt) => format!(
+            "{}.into_iter().next().map(typed::value).transpose()?.map(Box::new)",
+            found
+        ),
+        (Some(_), Mult::Many) => format!(
+            "{}.into_iter().map(typed::value).collect::<Result<_, _>>()?",
+            found
+        ),
+        (None, Mult::One) => format!("typed::text(typed::one(node, {}, {:?})?)", found, descr),
+        (None, Mult::Opt) => format!("{}.into_iter().next().map(typed::text)", found),
+        (None, Mult::Many) => format!("{}.into_iter().map(typed::text).collect()", found),
+    }
+}
+
+fn fields_decl(fields: &[Field], types: &HashMap<&str, String>, indent: &str) -> String {
+    fields
+        .iter()
+        .zip(field_idents(fields))
+        .map(|(f, ident)| format!("\n{}{}: {},", indent, ident, field_type(f, types)))
+        .collect()
+}
+
+fn fields_init(fields: &[Field], indent: &str) -> String {
+    fields
+        .iter()
+        .zip(field_idents(fields))
+        .map(|(f, ident)| format!("\n{}{}: {},", indent, ident, field_conversion(f)))
+        .collect()
+}
+
+//-----------------------------------------------------------------------
+//  RULES
+
+fn rule2code(name: &str, ri: &RuleInfo, types: &HashMap<&str, String>) -> String {
+    match unwrap(&ri.expr) {
+        Expression::Or(MultiExpr(alternatives)) => enum2code(name, alternatives, types),
+        expr => struct2code(name, expr, types),
+    }
+}
+
+fn struct2code(name: &str, expr: &Expression, types: &HashMap<&str, String>) -> String {
+    let ty = &types[name];
+    let fields = fields(expr);
+    let (decl, init) = if fields.is_empty() {
+        (
+            format!("pub struct {}(pub String);", ty),
+            format!("{}(typed::text_all(&nodes))", ty),
+        )
+    } else {
+        (
+            format!(
+                "pub struct {} {{{}\n}}",
+                ty,
+                fields_decl(&fields, types, "    pub ")
+            ),
+            format!(
+                "{} {{{}\n        }}",
+                ty,
+                fields_init(&fields, "            ")
+            ),
+        )
+    };
+
+    format!(
+        "
+/// rule `{}`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+{}
+
+impl TryFrom<&yepp::Node> for {} {{
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {{
+        let nodes = typed::rule(node, {:?})?;
+        Ok({})
+    }}
+}}
+",
+        name, decl, ty, name, init
+    )
+}
+
+fn enum2code(name: &str, alternatives: &[Expression], types: &HashMap<&str, String>) -> String {
+    let ty = &types[name];
+    let mut used = HashSet::new();
+    let (variants, conversions): (Vec<String>, Vec<String>) = alternatives
+        .iter()
+        .enumerate()
+        .map(|(i, alt)| {
+            let alt_name = format!("Alt{}", i + 1);
+            let fields = fields(alt);
+            let (decl, init) = match unwrap(alt) {
+                Expression::RuleName(rule) if !used.contains(&types[rule.as_str()]) => {
+                    let variant = unique(&mut used, types[rule.as_str()].clone());
+                    (
+                        format!("{}(Box<{}>)", variant, variant),
+                        format!("{}(Box::new({}::try_from(nodes[0])?))", variant, variant),
+                    )
+                }
+                _ if fields.is_empty() => {
+                    let variant = unique(&mut used, alt_name);
+                    (
+                        format!("{}(String)", variant),
+                        format!("{}(typed::text_all(&nodes))", variant),
+                    )
+                }
+                _ => {
+                    let variant = unique(&mut used, alt_name);
+                    (
+                        format!(
+                            "{} {{{}\n    }}",
+                            variant,
+                            fields_decl(&fields, types, "        ")
+                        ),
+                        format!(
+                            "{} {{{}\n            }}",
+                            variant,
+                            fields_init(&fields, "                ")
+                        ),
+                    )
+                }
+            };
+            (
+                format!("\n    {},", decl),
+                format!(
+                    "
+        if typed::matches(&nodes, &{}) {{
+            return Ok({}::{});
+        }}",
+                    shape2code(alt),
+                    ty,
+                    init
+                ),
+            )
+        })
+        .unzip();
+
+    format!(
+        "
+/// rule `{}`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub enum {} {{{}
+}}
+
+impl TryFrom<&yepp::Node> for {} {{
+    type Error = typed::Error;
+
+    fn try_from(node: &yepp::Node) -> Result<Self, Self::Error> {{
+        let nodes = typed::rule(node, {:?})?;{}
+        Err(typed::Error::new(node, {:?}))
+    }}
+}}
+",
+        name,
+        ty,
+        variants.concat(),
+        ty,
+        name,
+        conversions.concat(),
+        format!("an alternative of rule {}", name)
+    )
+}
+
+//-----------------------------------------------------------------------
+//  SHAPES
+
+fn shape2code(expr: &Expression) -> String {
+    let list = |exprs: &[Expression]| exprs.iter().map(shape2code).collect::<Vec<_>>().join(", ");
+
+    match expr {
+        Expression::Simple(Atom::Literal(s)) => format!("typed::Shape::Lit({:?})", s),
+        Expression::Simple(Atom::Match(mrules)) => {
+            format!("typed::Shape::Match({:?}, &{:?})", mrules.0, mrules.1)
+        }
+        Expression::Simple(Atom::Dot) => "typed::Shape::Dot".to_string(),
+        Expression::Simple(Atom::Eof) => "typed::Shape::Eof".to_string(),
+        Expression::Simple(Atom::Expected(_)) => "typed::Shape::Never".to_string(),
+        Expression::Not(_) | Expression::Peek(_) => "typed::Shape::Empty".to_string(),
+        Expression::And(MultiExpr(exprs))
+        | Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr {
+            mexpr: MultiExpr(exprs),
+            ..
+        })) => match exprs.as_slice() {
+            [expr] => shape2code(expr),
+            _ => format!("typed::Shape::And(&[{}])", list(exprs)),
+        },
+        Expression::Or(MultiExpr(exprs)) => match exprs.as_slice() {
+            [expr] => shape2code(expr),
+            _ => format!("typed::Shape::Or(&[{}])", list(exprs)),
+        },
+        Expression::Repeat(rep) => format!(
+            "typed::Shape::Rep(&{}, {}, {:?})",
+            shape2code(&rep.expression),
+            rep.min.0,
+            rep.max.as_ref().map(|m| m.0)
+        ),
+        Expression::RuleName(rule) => format!("typed::Shape::Rule({:?})", rule),
+        Expression::MetaExpr(MetaExpr::Named(NamedExpr { name, expr })) => {
+            format!("typed::Shape::Named({:?}, &{})", name, shape2code(expr))
+        }
+    }
+}
@@ -1,8 +1,121 @@
 mod to_rules;
 
+use crate::ast::Node;
+use crate::parser::Possition;
+
+#[cfg(test)]
+mod test;
+
+/// errors are boxed, they are large
+pub(crate) type Result<T> = std::result::Result<T, Box<Error>>;
+
 #[derive(Debug)]
 /// IR error information
-pub struct Error(pub(crate) String);
+pub struct Error {
+    pub(crate) msg: String,
+    /// rule processing when the error was produced
+    pub(crate) rule: Option<String>,
+    /// possition of the rule on the peg source
+    pub(crate) pos: Option<Possition>,
+    /// line on the peg source where the rule is defined
+    pub(crate) line: String,
+}
+
+impl Error {
+    pub(crate) fn new(msg: String) -> Box<Self> {
+        Box::new(Error {
+            msg,
+            rule: None,
+            pos: None,
+            line: String::new(),
+        })
+    }
+
+    /// error message
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// rule processing when the error was produced
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    /// possition on the peg source of the rule with the error
+    pub fn pos(&self) -> Option<&Possition> {
+        self.pos.as_ref()
+    }
+
+    pub(crate) fn in_rule(mut self: Box<Self>, rule: &str) -> Box<Self> {
+        if self.rule.is_none() {
+            self.rule = Some(rule.to_string());
+        }
+        self
+    }
+
+    /// locate the rule on the peg source, using the peg ast
+    pub(crate) fn with_source(mut self: Box<Self>, peg_ast: &Node, peg: &str) -> Box<Self> {
+        if let Some(span) = self
+            .rule
+            .as_ref()
+            .and_then(|rule| find_rule_name(peg_ast, peg, rule))
+        {
+            let start = span.start().clone();
+            self.line = peg[start.start_line..]
+                .lines()
+                .next()
+                .unwrap_or("")
+                .to_string();
+            self.pos = Some(start);
+        }
+        self
+    }
+}
+
+//  rule definitions on peg ast are  Rule("rule") -> ... -> Rule("rule_name")
+fn find_rule_name<'a>(node: &'a Node, peg: &str, rule: &str) -> Option<&'a crate::ast::Span> {
+    fn first_rule_name(node: &Node) -> Option<&Node> {
+        match node {
+            Node::Rule((name, _, _)) if name == "rule_name" => Some(node),
+            Node::Rule((_, nodes, _)) | Node::Named((_, nodes, _)) => {
+                nodes.iter().find_map(first_rule_name)
+            }
+            Node::Transf2(t2) => t2.nodes.iter().find_map(first_rule_name),
+            Node::Val(_) | Node::Eof(_) => None,
+        }
+    }
+
+    let nodes = match node {
+        Node::Rule((name, _, _)) if name == "rule" => {
+            return first_rule_name(node)
+                .filter(|rn| rn.source(peg) == rule)
+                .map(|rn| rn.span());
+        }
+        Node::Rule((_, nodes, _)) | Node::Named((_, nodes, _)) => nodes,
+        Node::Transf2(t2) => &t2.nodes,
+        Node::Val(_) | Node::Eof(_) => return None,
+    };
+    nodes.iter().find_map(|n| find_rule_name(n, peg, rule))
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.msg)?;
+        if let Some(pos) = &self.pos {
+            let line = (pos.row + 1).to_string();
+            let margin = " ".repeat(line.len());
+            write!(f, "\n{}--> {}:{}", margin, line, pos.col + 1)?;
+            write!(f, "\n{} |", margin)?;
+            write!(f, "\n{} | {}", line, self.line)?;
+        }
+        if let Some(rule) = &self.rule {
+            write!(f, "\n = rule: {}", rule)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
 
 #[derive(Debug)]
 /// IR error information
@@ -25,9 +138,9 @@ impl Ir {
         }
     }
 
-    fn get(mut self) -> Result<(Ir, Command), Error> {
+    fn get(mut self) -> Result<(Ir, Command)> {
         if self.pos >= self.commands.len() {
-            Err(Error("next over finished program".to_string()))
+            Err(Error::new("next over finished program".to_string()))
         } else {
             let cmd = self.commands[self.pos].clone();
             self.pos += 1;
@@ -39,12 +152,12 @@ impl Ir {
         self.commands.get(self.pos).cloned()
     }
 
-    fn consume(self, val: &str) -> Result<Ir, Error> {
+    fn consume(self, val: &str) -> Result<Ir> {
         let (ir, cmd) = self.get()?;
         if cmd.0 == val {
             Ok(ir)
         } else {
-            Err(Error(format!("expected {}, received {}", val, cmd.0)))
+            Err(Error::new(format!("expected {}, received {}", val, cmd.0)))
         }
     }
 }
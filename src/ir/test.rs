@@ -0,0 +1,66 @@
+//-----------------------------------------------------------------------
+//
+//  mod ir  TEST
+//
+//-----------------------------------------------------------------------
+
+use super::Ir;
+
+#[test]
+fn test_get_rules_ok() {
+    let ir = Ir::new("RULE\nmain\nDESCR\n\nATOM\nLIT\nhello\nEOP");
+    let (rules, duplicated) = ir.get_rules().ok().unwrap();
+
+    assert!(rules.0.contains_key("main"));
+    assert!(duplicated.is_empty());
+}
+
+#[test]
+fn test_get_rules_unknown_command() {
+    let ir = Ir::new("RULE\nmain\nDESCR\n\nATOM\nLIT\nhello\nRULE\nsecond\nDESCR\n\nWRONG\nEOP");
+    let err = ir.get_rules().err().unwrap();
+
+    assert_eq!(err.msg(), "unknown cmd reading expression <WRONG>");
+    assert_eq!(err.rule(), Some("second"));
+    assert!(err.pos().is_none());
+}
+
+#[test]
+fn test_get_rules_unfinished() {
+    let ir = Ir::new("RULE\nmain\nDESCR");
+    let err = ir.get_rules().err().unwrap();
+
+    assert_eq!(err.msg(), "next over finished program");
+    assert_eq!(err.rule(), Some("main"));
+}
+
+#[test]
+fn test_get_rules_duplicated() {
+    let ir = Ir::new(
+        "RULE\nmain\nDESCR\n\nATOM\nLIT\nfirst\nRULE\nmain\nDESCR\n\nATOM\nLIT\nsecond\nEOP",
+    );
+    let (rules, duplicated) = ir.get_rules().ok().unwrap();
+
+    assert_eq!(duplicated, vec!["main".to_string()]);
+    assert_eq!(rules.0["main"].expr, lit!("first"));
+}
+
+#[test]
+fn test_get_rules_order() {
+    let ir = Ir::new(
+        "RULE\nzeta\nDESCR\n\nATOM\nLIT\nz\nRULE\nmain\nDESCR\n\nATOM\nLIT\nm\nRULE\nalpha\nDESCR\n\nATOM\nLIT\na\nRULE\nzeta\nDESCR\n\nATOM\nLIT\nz2\nEOP",
+    );
+    let (rules, _) = ir.get_rules().ok().unwrap();
+
+    assert_eq!(
+        rules.0.keys().collect::<Vec<_>>(),
+        vec!["main", "zeta", "alpha"]
+    );
+    assert_eq!(
+        crate::gcode::rust_from_rules(&rules),
+        r##"
+         r#"main"# => RuleInfo{ expr:lit!("m"), descr:None }
+       , r#"zeta"# => RuleInfo{ expr:lit!("z"), descr:None }
+       , r#"alpha"# => RuleInfo{ expr:lit!("a"), descr:None }"##
+    );
+}
@@ -1,29 +1,42 @@
-use crate::ir::{Command, Error, Ir};
+use crate::ir::{Command, Error, Ir, Result};
 use crate::parser::expression::{
-    Expression, MetaExpr, MultiExpr, NamedExpr, RepInfo, ReplItem, ReplTemplate, SetOfRules,
-    Transf2Expr,
+    Expression, MetaExpr, MultiExpr, NameIndex, NamedExpr, RepInfo, ReplCond, ReplItem,
+    ReplTemplate, SetOfRules, Transf2Expr,
 };
 use idata::cont::IVec;
 
 impl Ir {
     /// get rules from an Ir code
-    pub(crate) fn get_rules(self) -> Result<SetOfRules, Error> {
-        let (_ir, rules) = get_rule_rec(self, SetOfRules::empty())?;
-        Ok(rules)
+    /// and the names of rules defined more than once
+    /// (first definition is the one kept)
+    ///
+    /// The rules keep the order of definition, but `main` is the first one
+    pub(crate) fn get_rules(self) -> Result<(SetOfRules, Vec<String>)> {
+        let (_ir, rules, duplicated) = get_rule_rec(self, SetOfRules::empty(), vec![])?;
+        Ok((rules.main_first(), duplicated))
     }
 }
 
-fn get_rule_rec(ir: Ir, rules: SetOfRules) -> Result<(Ir, SetOfRules), Error> {
+fn get_rule_rec(
+    ir: Ir,
+    rules: SetOfRules,
+    duplicated: Vec<String>,
+) -> Result<(Ir, SetOfRules, Vec<String>)> {
     if ir.peek() == Some(Command("EOP".to_string())) {
-        Ok((ir, rules))
+        Ok((ir, rules, duplicated))
     } else {
         let (ir, rule) = get_rule(ir)?;
+        let duplicated = rule
+            .0
+            .keys()
+            .filter(|name| rules.0.contains_key(*name))
+            .fold(duplicated, |acc, name| acc.ipush(name.clone()));
         let rules = rules.merge(rule);
-        get_rule_rec(ir, rules)
+        get_rule_rec(ir, rules, duplicated)
     }
 }
 
-fn get_expr(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_expr(ir: Ir) -> Result<(Ir, Expression)> {
     //  ATOM
     //  LIT
     //  literal
@@ -45,12 +58,15 @@ fn get_expr(ir: Ir) -> Result<(Ir, Expression), Error> {
         "EXPECTED" => get_expected(ir),
         "TRANSF2" => get_transf2(ir),
         "PEEK" => check_peek(ir),
-        other => Err(Error(format!("unknown cmd reading expression <{}>", other))),
+        other => Err(Error::new(format!(
+            "unknown cmd reading expression <{}>",
+            other
+        ))),
     }
 }
 
-fn get_transf2(ir: Ir) -> Result<(Ir, Expression), Error> {
-    let (ir, repl_templ) = get_transf2_items_rec(ir, ReplTemplate::empty())?;
+fn get_transf2(ir: Ir) -> Result<(Ir, Expression)> {
+    let (ir, repl_templ) = get_transf2_items_rec(ir, ReplTemplate::empty(), "EOTRANSF2")?;
     let (ir, expr) = get_expr(ir)?;
     Ok((
         ir,
@@ -61,7 +77,7 @@ fn get_transf2(ir: Ir) -> Result<(Ir, Expression), Error> {
     ))
 }
 
-fn get_transf2_item(ir: Ir) -> Result<(Ir, ReplItem), Error> {
+fn get_transf2_item(ir: Ir) -> Result<(Ir, ReplItem)> {
     //  TEXT
     //  txt
     //
@@ -79,6 +95,28 @@ fn get_transf2_item(ir: Ir) -> Result<(Ir, ReplItem), Error> {
                 let (ir, otxt) = get_transf2_named(ir)?;
                 Ok((ir, ReplItem::ByNameOpt(otxt)))
             }
+            "NAMED_JOIN" => {
+                let (ir, name) = get_transf2_named(ir)?;
+                let (ir, sep) = ir.get()?;
+                Ok((ir, ReplItem::ByNameJoin(name, unescape(&sep.0))))
+            }
+            "NAMED_COUNT" => {
+                let (ir, name) = get_transf2_named(ir)?;
+                Ok((ir, ReplItem::ByNameCount(name)))
+            }
+            "NAMED_INDEX" => {
+                let (ir, name) = get_transf2_named(ir)?;
+                let (ir, idx) = ir.get()?;
+                let idx = match idx.0.as_ref() {
+                    "first" => NameIndex::First,
+                    "last" => NameIndex::Last,
+                    n => NameIndex::Nth(n.parse().map_err(|e| {
+                        Error::new(format!("Failed reading name index... {:?} ", e))
+                    })?),
+                };
+                Ok((ir, ReplItem::ByNameIndex(name, idx)))
+            }
+            "IF" => get_transf2_if(ir),
             "POS" => {
                 let (ir, pos) = get_transf2_pos(ir)?;
                 Ok((ir, ReplItem::ByPos(pos)))
@@ -87,49 +125,103 @@ fn get_transf2_item(ir: Ir) -> Result<(Ir, ReplItem), Error> {
                 let (ir, txt) = get_transf2_named(ir)?;
                 Ok((ir, ReplItem::Function(txt)))
             }
-            other => Err(Error(format!(
+            other => Err(Error::new(format!(
                 "unxecpected command reading get_transf2 <{}>",
                 other
             ))),
         }
     } else {
-        Err(Error("Missing transf2 item".to_string()))
+        Err(Error::new("Missing transf2 item".to_string()))
     }
 }
 
-fn get_transf2_items_rec(ir: Ir, repl_templ: ReplTemplate) -> Result<(Ir, ReplTemplate), Error> {
-    if ir.peek() == Some(Command("EOTRANSF2".to_string())) {
+/// read items till `end` command
+fn get_transf2_items_rec(
+    ir: Ir,
+    repl_templ: ReplTemplate,
+    end: &str,
+) -> Result<(Ir, ReplTemplate)> {
+    if ir.peek() == Some(Command(end.to_string())) {
         let (ir, _) = ir.get()?;
         Ok((ir, repl_templ))
     } else {
         let (ir, item) = get_transf2_item(ir)?;
-        get_transf2_items_rec(ir, repl_templ.ipush(item))
+        get_transf2_items_rec(ir, repl_templ.ipush(item), end)
     }
 }
 
-fn get_transf2_named(ir: Ir) -> Result<(Ir, String), Error> {
+fn get_transf2_if(ir: Ir) -> Result<(Ir, ReplItem)> {
+    //  IF
+    //  NAMED | EQ
+    //  name
+    //  literal     (if EQ)
+    //  <items>
+    //  ELSE
+    //  <items>
+    //  END_IF
+    let (ir, _) = ir.get()?;
+    let (ir, cond_kind) = ir.get()?;
+    let (ir, name) = ir.get()?;
+    let (ir, cond) = match cond_kind.0.as_ref() {
+        "NAMED" => (ir, ReplCond::Named(name.0)),
+        "EQ" => {
+            let (ir, lit) = ir.get()?;
+            (ir, ReplCond::NamedEq(name.0, unescape(&lit.0)))
+        }
+        other => {
+            return Err(Error::new(format!(
+                "unxecpected condition on replace template <{}>",
+                other
+            )))
+        }
+    };
+    let (ir, then) = get_transf2_items_rec(ir, ReplTemplate::empty(), "ELSE")?;
+    let (ir, else_) = get_transf2_items_rec(ir, ReplTemplate::empty(), "END_IF")?;
+    Ok((ir, ReplItem::If(cond, then, else_)))
+}
+
+fn get_transf2_named(ir: Ir) -> Result<(Ir, String)> {
     let (ir, _) = ir.get()?;
     let (ir, named) = ir.get()?;
     Ok((ir, named.0))
 }
 
-fn get_transf2_pos(ir: Ir) -> Result<(Ir, usize), Error> {
+fn get_transf2_pos(ir: Ir) -> Result<(Ir, usize)> {
     let (ir, _) = ir.get()?;
     let (ir, str_pos) = ir.get()?;
     let pos = str_pos
         .0
         .parse()
-        .map_err(|e| Error(format!("Failed reading pos transformation... {:?} ", e)))?;
+        .map_err(|e| Error::new(format!("Failed reading pos transformation... {:?} ", e)))?;
     Ok((ir, pos))
 }
 
-fn get_transf2_text(ir: Ir) -> Result<(Ir, String), Error> {
+/// escaped chars on template literals  ie: `\n`
+fn unescape(txt: &str) -> String {
+    let mut result = String::new();
+    let mut chars = txt.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some('t') => result.push('\t'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            },
+            ch => result.push(ch),
+        }
+    }
+    result
+}
+
+fn get_transf2_text(ir: Ir) -> Result<(Ir, String)> {
     let (ir, _) = ir.get()?;
     let (ir, txt) = ir.get()?;
     Ok((ir, txt.0))
 }
 
-fn get_expected(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_expected(ir: Ir) -> Result<(Ir, Expression)> {
     //  <err message>
 
     let (ir, msg) = ir.get()?;
@@ -137,7 +229,7 @@ fn get_expected(ir: Ir) -> Result<(Ir, Expression), Error> {
     Ok((ir, expr))
 }
 
-fn get_negate(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_negate(ir: Ir) -> Result<(Ir, Expression)> {
     //  <expr>
 
     let (ir, expr) = get_expr(ir)?;
@@ -145,7 +237,7 @@ fn get_negate(ir: Ir) -> Result<(Ir, Expression), Error> {
     Ok((ir, expr))
 }
 
-fn check_peek(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn check_peek(ir: Ir) -> Result<(Ir, Expression)> {
     //  <expr>
 
     let (ir, expr) = get_expr(ir)?;
@@ -153,7 +245,7 @@ fn check_peek(ir: Ir) -> Result<(Ir, Expression), Error> {
     Ok((ir, expr))
 }
 
-fn get_named(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_named(ir: Ir) -> Result<(Ir, Expression)> {
     //  name
     //  <expr>
 
@@ -166,7 +258,7 @@ fn get_named(ir: Ir) -> Result<(Ir, Expression), Error> {
     Ok((ir, expr))
 }
 
-fn get_match(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_match(ir: Ir) -> Result<(Ir, Expression)> {
     //  CHARS
     //  ASDFASDF
     //  BETWEEN
@@ -183,7 +275,7 @@ fn get_match(ir: Ir) -> Result<(Ir, Expression), Error> {
     Ok((ir, expr))
 }
 
-fn get_match_chars(ir: Ir) -> Result<(Ir, String), Error> {
+fn get_match_chars(ir: Ir) -> Result<(Ir, String)> {
     if Some(Command("CHARS".to_string())) == ir.peek() {
         let (ir, _) = ir.get()?;
         let (ir, c) = ir.get()?;
@@ -193,7 +285,7 @@ fn get_match_chars(ir: Ir) -> Result<(Ir, String), Error> {
     }
 }
 
-fn get_match_between(ir: Ir) -> Result<(Ir, Vec<(char, char)>), Error> {
+fn get_match_between(ir: Ir) -> Result<(Ir, Vec<(char, char)>)> {
     if Some(Command("BETW".to_string())) == ir.peek() {
         let (ir, _) = ir.get()?;
         let (ir, v) = get_between_rec(ir, vec![])?;
@@ -203,7 +295,7 @@ fn get_match_between(ir: Ir) -> Result<(Ir, Vec<(char, char)>), Error> {
     }
 }
 
-fn get_between_rec(ir: Ir, v: Vec<(char, char)>) -> Result<(Ir, Vec<(char, char)>), Error> {
+fn get_between_rec(ir: Ir, v: Vec<(char, char)>) -> Result<(Ir, Vec<(char, char)>)> {
     if ir.peek() == Some(Command("EOBETW".to_string())) {
         let (ir, _) = ir.get()?;
         Ok((ir, v))
@@ -213,7 +305,7 @@ fn get_between_rec(ir: Ir, v: Vec<(char, char)>) -> Result<(Ir, Vec<(char, char)
         let fc = |s: String| {
             s.chars()
                 .next()
-                .ok_or_else(|| Error(format!("expected char received <{}>", s)))
+                .ok_or_else(|| Error::new(format!("expected char received <{}>", s)))
         };
 
         let ch1 = fc(ch1.0)?;
@@ -224,14 +316,14 @@ fn get_between_rec(ir: Ir, v: Vec<(char, char)>) -> Result<(Ir, Vec<(char, char)
     }
 }
 
-fn get_repeat(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_repeat(ir: Ir) -> Result<(Ir, Expression)> {
     //  1
     //  inf
     //  expr
     let (ir, min) = ir.get()?;
     let min = match min.0.parse::<usize>() {
         Ok(v) => Ok(v),
-        Err(e) => Err(Error(format!("getting min size {}", e))),
+        Err(e) => Err(Error::new(format!("getting min size {}", e))),
     }?;
     let (ir, max) = ir.get()?;
     let max = if max.0 == "inf" {
@@ -239,7 +331,7 @@ fn get_repeat(ir: Ir) -> Result<(Ir, Expression), Error> {
     } else {
         match max.0.parse::<usize>() {
             Ok(v) => Ok(Some(v)),
-            Err(e) => Err(Error(format!("getting max size {}", e))),
+            Err(e) => Err(Error::new(format!("getting max size {}", e))),
         }
     }?;
     let (ir, expr) = get_expr(ir)?;
@@ -249,11 +341,11 @@ fn get_repeat(ir: Ir) -> Result<(Ir, Expression), Error> {
     ))
 }
 
-fn get_mexpr(ir: Ir) -> Result<(Ir, MultiExpr), Error> {
+fn get_mexpr(ir: Ir) -> Result<(Ir, MultiExpr)> {
     get_mexpr_rec(ir, MultiExpr::new(vec![]))
 }
 
-fn get_mexpr_rec(ir: Ir, me: MultiExpr) -> Result<(Ir, MultiExpr), Error> {
+fn get_mexpr_rec(ir: Ir, me: MultiExpr) -> Result<(Ir, MultiExpr)> {
     if ir.peek() == Some(Command("CLOSE_MEXPR".to_string())) {
         let (ir, _) = ir.get()?;
         Ok((ir, me))
@@ -264,7 +356,7 @@ fn get_mexpr_rec(ir: Ir, me: MultiExpr) -> Result<(Ir, MultiExpr), Error> {
     }
 }
 
-fn get_atom(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_atom(ir: Ir) -> Result<(Ir, Expression)> {
     //  LIT
     //  literal
     let (ir, cmd) = ir.get()?;
@@ -273,23 +365,23 @@ fn get_atom(ir: Ir) -> Result<(Ir, Expression), Error> {
         "RULREF" => get_rulref(ir),
         "DOT" => Ok((ir, dot!())),
         "EOF" => Ok((ir, eof!())),
-        other => Err(Error(format!("unknown cmd reading atom <{}>", other))),
+        other => Err(Error::new(format!("unknown cmd reading atom <{}>", other))),
     }
 }
 
-fn get_rulref(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_rulref(ir: Ir) -> Result<(Ir, Expression)> {
     //  name
     let (ir, cmd) = ir.get()?;
     Ok((ir, ref_rule!(cmd.0)))
 }
 
-fn get_lit(ir: Ir) -> Result<(Ir, Expression), Error> {
+fn get_lit(ir: Ir) -> Result<(Ir, Expression)> {
     //  literal
     let (ir, cmd) = ir.get()?;
     Ok((ir, lit!(cmd.0)))
 }
 
-fn get_rule(ir: Ir) -> Result<(Ir, SetOfRules), Error> {
+fn get_rule(ir: Ir) -> Result<(Ir, SetOfRules)> {
     //  RULE
     //  name
     //  DESCR
@@ -301,6 +393,10 @@ fn get_rule(ir: Ir) -> Result<(Ir, SetOfRules), Error> {
 
     let (ir, name) = ir.get()?;
 
+    get_rule_body(ir, &name.0).map_err(|e| e.in_rule(&name.0))
+}
+
+fn get_rule_body(ir: Ir, name: &str) -> Result<(Ir, SetOfRules)> {
     let ir = ir.consume("DESCR")?;
 
     let (ir, descr) = ir.get()?;
@@ -313,5 +409,5 @@ fn get_rule(ir: Ir) -> Result<(Ir, SetOfRules), Error> {
 
     let (ir, expr) = get_expr(ir)?;
 
-    Ok((ir, rules! { &name.0 => RuleInfo{expr, descr} }))
+    Ok((ir, rules! { name => RuleInfo{expr, descr} }))
 }
@@ -38,7 +38,9 @@
 //!
 
 extern crate idata;
-extern crate im;
+extern crate indexmap;
+//  generated code uses `yepp::` paths, also the one inside this crate
+extern crate self as yepp;
 
 use std::result;
 
@@ -47,11 +49,15 @@ use std::result;
 #[macro_use]
 pub(crate) mod macros;
 pub(crate) mod ast;
+pub(crate) mod check;
 pub(crate) mod gcode;
 pub(crate) mod ir;
 pub(crate) mod parser;
 pub(crate) mod rules_for_peg;
 
+#[cfg(test)]
+mod test;
+
 // -------------------------------------------------------------------------------------
 //  T Y P E S
 
@@ -61,6 +67,27 @@ pub(crate) mod rules_for_peg;
 // -------------------------------------------------------------------------------------
 //  A P I
 
+pub use crate::ast::replace::FnContext;
+pub use crate::ast::typed;
+pub use crate::ast::{Node, Span};
+pub use crate::check::{Issue, IssueKind};
+pub use crate::gcode::build::{Build, BuildError};
+pub use crate::ir::Error as IrError;
+pub use crate::parser::expression::SetOfRules;
+pub use crate::parser::specialized;
+pub use crate::parser::Error as ParserError;
+pub use crate::parser::{ErrPriority, ErrorAlternatives, Possition};
+
+/// Types to write the rules by hand, usually through the macros
+/// (`rules!`, `and!`, `or!`, `lit!`...). Generated code uses them too
+pub mod grammar {
+    pub use crate::parser::atom::{Atom, MatchRules};
+    pub use crate::parser::expression::{
+        Expression, MetaExpr, MultiExpr, NameIndex, NamedExpr, RepInfo, ReplCond, ReplItem,
+        ReplTemplate, RuleInfo, SetOfRules, Transf2Expr,
+    };
+}
+
 /// Peg type for fluent API
 pub struct Peg<'a>(&'a str);
 
@@ -68,13 +95,39 @@ pub struct Peg<'a>(&'a str);
 #[derive(Debug)]
 pub enum Error {
     /// error on parsing
-    ParserErr(crate::parser::Error),
+    ParserErr(Box<crate::parser::Error>),
     /// error on replace
     ReplaceErr(String),
     /// error processing IR
-    IrErr(crate::ir::Error),
+    IrErr(Box<crate::ir::Error>),
+    /// problems found checking the grammar
+    GrammarErr(Vec<crate::check::Issue>),
+    /// the rule to start parsing is not on the set of rules
+    MissingStartRule(String),
+    /// error reading or writing a file
+    IoErr(std::path::PathBuf, std::io::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::ParserErr(e) => write!(f, "{}", e),
+            Error::ReplaceErr(e) => write!(f, "error on replace: {}", e),
+            Error::IrErr(e) => write!(f, "error processing IR: {}", e),
+            Error::GrammarErr(issues) => {
+                write!(f, "errors on grammar:")?;
+                issues
+                    .iter()
+                    .try_for_each(|issue| write!(f, "\n  {}", issue))
+            }
+            Error::MissingStartRule(r) => write!(f, "missing start rule {}", r),
+            Error::IoErr(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
 }
 
+impl std::error::Error for Error {}
+
 impl<'a> Peg<'a> {
     /// create an instance of Peg
     pub fn new(txt: &'a str) -> Self {
@@ -85,47 +138,346 @@ impl<'a> Peg<'a> {
     pub fn gen_rules(&self) -> result::Result<crate::parser::expression::SetOfRules, Error> {
         use crate::ir::Ir;
 
-        let irtxt = crate::rules_for_peg::rules().parse(self.0)?.replace(None)?;
+        let peg_ast = crate::rules_for_peg::rules().parse(self.0)?;
+        let irtxt = peg_ast.replace(None)?;
         let ir = Ir::new(&irtxt.str());
 
-        Ok(ir.get_rules().unwrap())
+        let (rules, duplicated) = ir
+            .get_rules()
+            .map_err(|e| Error::IrErr(e.with_source(&peg_ast, self.0)))?;
+
+        let issues = check::check_rules(&rules, &duplicated);
+        if issues.is_empty() {
+            Ok(rules)
+        } else {
+            Err(Error::GrammarErr(issues))
+        }
     }
 }
 
 impl crate::parser::expression::SetOfRules {
     /// parse from a set of rules (fluent API)
     pub fn parse(&self, text: &str) -> Result<ast::Node, Error> {
-        crate::parse(text, self).map_err(Error::ParserErr)
+        self.parse_from("main", text)
     }
 
     /// parse with debug info
     pub fn parse_debug(&self, text: &str) -> Result<ast::Node, Error> {
-        crate::parse_debug(text, self).map_err(Error::ParserErr)
+        self.parse_debug_from("main", text)
+    }
+
+    /// parse starting on `rule` instead of `main`
+    ///
+    /// Usefull to parse fragments, or to test a rule in isolation
+    pub fn parse_from(&self, rule: &str, text: &str) -> Result<ast::Node, Error> {
+        self.check_start_rule(rule)?;
+        crate::parse(text, self, rule).map_err(Error::ParserErr)
+    }
+
+    /// parse starting on `rule`, with debug info
+    pub fn parse_debug_from(&self, rule: &str, text: &str) -> Result<ast::Node, Error> {
+        self.check_start_rule(rule)?;
+        crate::parse_debug(text, self, rule).map_err(Error::ParserErr)
+    }
+
+    /// parse the start of `text`, the rest doesn't need to match
+    ///
+    /// It returns the ast, the possition where the match ended and
+    /// the remaining text, to continue from there
+    pub fn parse_prefix<'t>(
+        &self,
+        text: &'t str,
+    ) -> Result<(ast::Node, Possition, &'t str), Error> {
+        self.parse_prefix_from("main", text)
+    }
+
+    /// as `parse_prefix`, starting on `rule` instead of `main`
+    pub fn parse_prefix_from<'t>(
+        &self,
+        rule: &str,
+        text: &'t str,
+    ) -> Result<(ast::Node, Possition, &'t str), Error> {
+        self.check_start_rule(rule)?;
+        let (ast, end) = crate::parse_prefix(text, self, rule).map_err(Error::ParserErr)?;
+        let rest = &text[end.n..];
+        Ok((ast, end, rest))
+    }
+
+    fn check_start_rule(&self, rule: &str) -> Result<(), Error> {
+        if self.0.contains_key(rule) {
+            Ok(())
+        } else {
+            Err(Error::MissingStartRule(rule.to_string()))
+        }
+    }
+
+    /// look for references to undefined rules, missing `main`,
+    /// repetitions of expressions that can match empty,
+    /// left recursions without alternative to start and names used
+    /// on replace templates not available on their expressions
+    ///
+    /// `Peg::gen_rules` runs it, and also reports rules defined more than once
+    pub fn check(&self) -> Vec<Issue> {
+        check::check_rules(self, &[])
+    }
+
+    /// look for rules not reachable from `main` and `or` alternatives
+    /// that never match because a previous one matches first  ie: `'a' / 'ab'`
+    ///
+    /// The grammar works, `gen_rules` doesn't run it
+    pub fn lint(&self) -> Vec<Issue> {
+        self.lint_from("main")
+    }
+
+    /// as `lint`, with the rules reachable from `rule` instead of `main`
+    /// (grammars parsed with `parse_from`)
+    pub fn lint_from(&self, rule: &str) -> Vec<Issue> {
+        check::lint::lint(self, rule)
+    }
+
+    /// rules that can succeed without consuming input
+    pub fn nullable_rules(&self) -> Vec<&str> {
+        let mut nullables = check::nullable::nullable_rules(self)
+            .into_iter()
+            .collect::<Vec<_>>();
+        nullables.sort_unstable();
+        nullables
+    }
+
+    /// left recursive cycles, as a path of rules
+    ///
+    /// ie: `a = b 'x'`, `b = a? 'y'` gives `["a", "b"]`  (a -> b -> a)
+    ///
+    /// Left recursion is supported, this is informative
+    pub fn left_recursions(&self) -> Vec<Vec<String>> {
+        check::left_rec::left_recursions(self)
+    }
+
+    /// rust code of a parser specialized for these rules, with a function
+    /// per rule (`r_<rule>`), `parse(text)` starting on `main` and
+    /// `parse_from(rule, text)`
+    ///
+    /// It gives the same `ast` and errors than `parse` (not the debug info).
+    /// Left recursion is not supported
+    pub fn specialized_parser(&self) -> Result<String, Error> {
+        let issues = check::check_rules(self, &[])
+            .into_iter()
+            .chain(self.left_recursions().into_iter().map(|cycle| Issue {
+                rule: cycle.first().cloned(),
+                kind: IssueKind::SpecializedLeftRecursion(cycle),
+            }))
+            .collect::<Vec<_>>();
+
+        if issues.is_empty() {
+            Ok(gcode::specialized::rust_specialized(self))
+        } else {
+            Err(Error::GrammarErr(issues))
+        }
+    }
+
+    /// rust code of typed `ast` structs and enums, one per rule, with a
+    /// `TryFrom<&Node>` conversion from the `ast` given by `parse`
+    ///
+    /// Named captures and references to rules are fields, alternatives (`or`)
+    /// are enum variants and repetitions are `Vec`
+    pub fn typed_ast(&self) -> Result<String, Error> {
+        let issues = check::check_rules(self, &[]);
+        if issues.is_empty() {
+            Ok(gcode::typed::rust_typed(self))
+        } else {
+            Err(Error::GrammarErr(issues))
+        }
+    }
+
+    /// inputs of `corpus` where `specialized` (the `parse` of a specialized parser)
+    /// doesn't give the same `ast` or error than the interpreter, with the
+    /// path and span of the first difference
+    pub fn compare_specialized<'t>(
+        &self,
+        specialized: impl Fn(&str) -> Result<ast::Node, Box<ParserError>>,
+        corpus: &[&'t str],
+    ) -> Vec<parser::specialized::Mismatch<'t>> {
+        self.compare_specialized_from("main", specialized, corpus)
+    }
+
+    /// as `compare_specialized`, starting on `rule` instead of `main`
+    /// (`specialized` would be the `parse_from` of the specialized parser)
+    pub fn compare_specialized_from<'t>(
+        &self,
+        rule: &str,
+        specialized: impl Fn(&str) -> Result<ast::Node, Box<ParserError>>,
+        corpus: &[&'t str],
+    ) -> Vec<parser::specialized::Mismatch<'t>> {
+        corpus
+            .iter()
+            .filter_map(|text| {
+                parser::specialized::mismatch(
+                    text,
+                    &crate::parse(text, self, rule),
+                    &specialized(text),
+                )
+            })
+            .collect()
+    }
+
+    /// parse caching the result of each rule on each possition (packrat)
+    ///
+    /// It avoids exponential time on grammars with alternatives sharing
+    /// prefixes, at the cost of memory. The cache lives just for this parse
+    pub fn parse_memo(&self, text: &str) -> Result<ast::Node, Error> {
+        self.parse_memo_from("main", text)
+    }
+
+    /// parse with memoization, starting on `rule`
+    pub fn parse_memo_from(&self, rule: &str, text: &str) -> Result<ast::Node, Error> {
+        self.check_start_rule(rule)?;
+        crate::parse_memo(text, self, rule, false).map_err(Error::ParserErr)
+    }
+
+    /// parse with memoization and debug info
+    pub fn parse_memo_debug(&self, text: &str) -> Result<ast::Node, Error> {
+        self.parse_memo_debug_from("main", text)
+    }
+
+    /// parse with memoization and debug info, starting on `rule`
+    pub fn parse_memo_debug_from(&self, rule: &str, text: &str) -> Result<ast::Node, Error> {
+        self.check_start_rule(rule)?;
+        crate::parse_memo(text, self, rule, true).map_err(Error::ParserErr)
     }
 }
 
 ///  given a file or dir, process the .peg files
 ///  generating rust code
-pub fn process_peg_files(dir: &std::path::Path) {
+pub fn process_peg_files(dir: &std::path::Path) -> Result<(), Error> {
     gcode::proc_peg_files::run(dir)
 }
 
 /// process and generate source code for file
 /// even if it hasn't been updated
-pub fn process_peg_files_force(dir: &std::path::Path) {
+pub fn process_peg_files_force(dir: &std::path::Path) -> Result<(), Error> {
     gcode::proc_peg_files::run_force(dir)
 }
 
+/// User defined functions called from replace templates  `$(:name arg1 arg2)`
+///
+/// It's implemented for closures
+/// `FnMut(&str, &[&str], &FnContext) -> Result<Option<String>, String>`
+/// receiving the function name and the arguments (separated by spaces on template)
+///
+/// It's called before the predefined functions, so it can redefine them,
+/// but `indent` and `dedent` (they change the output indentation)
+///
+/// Returning `Ok(None)` the internal functions will be tried (`endl`, `spc`...)
+/// and returning `Err` will abort the replace
+pub trait ReplaceFn {
+    /// call the function `name` with `args`
+    fn call(
+        &mut self,
+        name: &str,
+        args: &[&str],
+        context: &FnContext,
+    ) -> Result<Option<String>, String>;
+}
+
+impl<F> ReplaceFn for F
+where
+    F: FnMut(&str, &[&str], &FnContext) -> Result<Option<String>, String>,
+{
+    fn call(
+        &mut self,
+        name: &str,
+        args: &[&str],
+        context: &FnContext,
+    ) -> Result<Option<String>, String> {
+        self(name, args, context)
+    }
+}
+
 /// Type to user defined funtions callbacks
+///
+/// It receives the full function text (name and arguments)
 pub struct FnCallBack(pub fn(&str) -> Option<String>);
 
+impl ReplaceFn for FnCallBack {
+    fn call(&mut self, name: &str, args: &[&str], _: &FnContext) -> Result<Option<String>, String> {
+        let fn_txt = std::iter::once(name)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(self.0(&fn_txt))
+    }
+}
+
 impl ast::Node {
     /// run the tree replacing acording the rules
+    ///
+    /// Missing names and unknown functions write a mark on output
+    /// (`name<x/missing>`, `?unknown_fn?<f>`)
     pub fn replace(
         &self,
-        fcallback: Option<&FnCallBack>,
+        fcallback: Option<&mut dyn ReplaceFn>,
+    ) -> Result<crate::ast::replace::Replaced, Error> {
+        self.replace_mode(fcallback, false)
+    }
+
+    /// run the tree replacing acording the rules
+    ///
+    /// Missing names and unknown functions are errors
+    pub fn replace_strict(
+        &self,
+        fcallback: Option<&mut dyn ReplaceFn>,
+    ) -> Result<crate::ast::replace::Replaced, Error> {
+        self.replace_mode(fcallback, true)
+    }
+
+    fn replace_mode(
+        &self,
+        fcallback: Option<&mut dyn ReplaceFn>,
+        strict: bool,
     ) -> Result<crate::ast::replace::Replaced, Error> {
-        ast::replace::replace(&self, fcallback).map_err(Error::ReplaceErr)
+        match fcallback {
+            Some(fcallback) => ast::replace::replace(self, fcallback, strict),
+            None => ast::replace::replace(self, &mut ast::replace::NoFn, strict),
+        }
+        .map_err(Error::ReplaceErr)
+    }
+
+    /// run the tree replacing acording the rules, writing the output
+    /// as it's produced, instead of keeping all of it in memory
+    ///
+    /// Nodes are written where their template uses them, their text is
+    /// kept just when its value is needed (conditions and functions).
+    /// On failure, part of the output could have been written.
+    /// `out` is not buffered here, consider a `std::io::BufWriter`
+    pub fn replace_to(
+        &self,
+        out: &mut impl std::io::Write,
+        fcallback: Option<&mut dyn ReplaceFn>,
+    ) -> Result<(), Error> {
+        self.replace_to_mode(out, fcallback, false)
+    }
+
+    /// as `replace_to`, but missing names and unknown functions are errors
+    pub fn replace_to_strict(
+        &self,
+        out: &mut impl std::io::Write,
+        fcallback: Option<&mut dyn ReplaceFn>,
+    ) -> Result<(), Error> {
+        self.replace_to_mode(out, fcallback, true)
+    }
+
+    fn replace_to_mode(
+        &self,
+        out: &mut impl std::io::Write,
+        fcallback: Option<&mut dyn ReplaceFn>,
+        strict: bool,
+    ) -> Result<(), Error> {
+        let mut out = ast::replace::WriteOutput(out);
+        match fcallback {
+            Some(fcallback) => ast::replace::replace_to(self, fcallback, strict, &mut out),
+            None => ast::replace::replace_to(self, &mut ast::replace::NoFn, strict, &mut out),
+        }
+        .map_err(Error::ReplaceErr)
     }
 }
 
@@ -134,34 +486,71 @@ impl ast::Node {
 
 // -------------------------------------------------------------------------------------
 
-fn parse(s: &str, rules: &parser::expression::SetOfRules) -> Result<ast::Node, parser::Error> {
-    parse_with_debug(s, rules, false)
+fn parse(
+    s: &str,
+    rules: &parser::expression::SetOfRules,
+    start_rule: &str,
+) -> Result<ast::Node, Box<parser::Error>> {
+    parse_with_debug(s, rules, start_rule, false)
 }
 
 fn parse_debug(
     s: &str,
     rules: &parser::expression::SetOfRules,
-) -> Result<ast::Node, parser::Error> {
-    parse_with_debug(s, rules, true)
+    start_rule: &str,
+) -> Result<ast::Node, Box<parser::Error>> {
+    parse_with_debug(s, rules, start_rule, true)
 }
 
-fn parse_with_debug(
+fn parse_memo(
     s: &str,
     rules: &parser::expression::SetOfRules,
+    start_rule: &str,
     debug: bool,
-) -> Result<ast::Node, parser::Error> {
-    let (st, ast) = if debug {
-        parser::expression::parse(parser::Status::init_debug(s, &rules, debug))?
+) -> Result<ast::Node, Box<parser::Error>> {
+    let status = if debug {
+        parser::Status::init_debug(s, rules, debug)
     } else {
-        parser::expression::parse(parser::Status::init(s, &rules))?
+        parser::Status::init(s, rules)
     };
+    parse_status(s, status.with_memo(), start_rule)
+}
+
+fn parse_with_debug(
+    s: &str,
+    rules: &parser::expression::SetOfRules,
+    start_rule: &str,
+    debug: bool,
+) -> Result<ast::Node, Box<parser::Error>> {
+    if debug {
+        parse_status(s, parser::Status::init_debug(s, rules, debug), start_rule)
+    } else {
+        parse_status(s, parser::Status::init(s, rules), start_rule)
+    }
+}
+
+fn parse_prefix(
+    s: &str,
+    rules: &parser::expression::SetOfRules,
+    start_rule: &str,
+) -> Result<(ast::Node, Possition), Box<parser::Error>> {
+    let (st, ast) = parser::expression::parse(parser::Status::init(s, rules), start_rule)?;
+    Ok((ast, st.pos))
+}
+
+fn parse_status(
+    s: &str,
+    status: parser::Status,
+    start_rule: &str,
+) -> Result<ast::Node, Box<parser::Error>> {
+    let (st, ast) = parser::expression::parse(status, start_rule)?;
     match (st.pos.n == s.len(), st.potential_error.clone()) {
         (true, _) => Ok(ast),
         (false, Some(e)) => Err(e),
-        (false, None) => Err(parser::Error::from_status_normal_simple(
+        (false, None) => Err(Box::new(parser::Error::from_status_normal_simple(
             &st,
             "not consumed full input",
-        )),
+        ))),
     }
 }
 
@@ -1,117 +1,139 @@
 // -------------------------------------------------------------------------------------
 //  M A C R O S
 
-#[cfg_attr(feature = "cargo-clippy", allow(clippy::let_and_return))]
+/// Build a `SetOfRules`  ie: `rules!("main" => RuleInfo::new(lit!("a")))`
+#[macro_export]
+#[allow(clippy::let_and_return)]
 macro_rules! rules {
     ($($n:expr => $ri:expr),*) => {{
-        use $crate::parser::expression::{self, RuleInfo};
-        use std::collections::HashMap;
+        #[allow(unused_imports)]
+        use $crate::grammar::{RuleInfo, SetOfRules};
 
-        let rules = expression::SetOfRules::new(HashMap::<String, RuleInfo>::new());
+        let rules = SetOfRules::empty();
         $(let rules = rules.add($n, $ri);)*
         rules
     }};
 }
 
+/// Literal expression  ie: `lit!("hello")`
+#[macro_export]
 macro_rules! lit {
     ($e:expr) => {{
-        $crate::parser::expression::Expression::Simple($crate::parser::atom::Atom::Literal(
-            $e.to_string(),
-        ))
+        $crate::grammar::Expression::Simple($crate::grammar::Atom::Literal($e.to_string()))
     }};
 }
 
+/// Expression failing with a message  ie: `expected!("a number")`
+#[macro_export]
 macro_rules! expected {
     ($e:expr) => {{
-        $crate::parser::expression::Expression::Simple($crate::parser::atom::Atom::Expected(
-            $e.to_string(),
-        ))
+        $crate::grammar::Expression::Simple($crate::grammar::Atom::Expected($e.to_string()))
     }};
 }
 
+/// Any char expression
+#[macro_export]
 macro_rules! dot {
     () => {{
-        $crate::parser::expression::Expression::Simple($crate::parser::atom::Atom::Dot)
+        $crate::grammar::Expression::Simple($crate::grammar::Atom::Dot)
     }};
 }
 
+/// End of input expression
+#[macro_export]
 macro_rules! eof {
     () => {{
-        $crate::parser::expression::Expression::Simple($crate::parser::atom::Atom::Eof)
+        $crate::grammar::Expression::Simple($crate::grammar::Atom::Eof)
     }};
 }
 
+/// Match a char from a list or ranges
+///
+/// ie: `ematch!(chlist "_", from 'a', to 'z', from '0', to '9')`
+#[macro_export]
 macro_rules! ematch {
     (chlist $chars:expr, $(from $from:expr,  to $to:expr),*) => {{
-        //use idata::cont::IVec;  //  pending macros by example 2.0
-        use $crate::parser;
-        let mut v = Vec::<(char, char)>::new();
-
-        //$(let v = v.ipush(($from, $to));)+  //  pending macros by example 2.0
-        $(v.push(($from, $to));)+
-        let amatch = parser::atom::Atom::Match(parser::atom::MatchRules::init($chars, v));
-        parser::expression::Expression::Simple(amatch)
+        use $crate::grammar::{Atom, Expression, MatchRules};
+        let v: Vec<(char, char)> = vec![$(($from, $to)),+];
+        let amatch = Atom::Match(MatchRules::init($chars, v));
+        Expression::Simple(amatch)
     }};
 
     (chlist $chars:expr, from2 $vfrom2:expr) => {{
-        use $crate::parser;
+        use $crate::grammar::{Atom, Expression, MatchRules};
 
-        let amatch = parser::atom::Atom::Match(parser::atom::MatchRules::init($chars, $vfrom2));
-        parser::expression::Expression::Simple(amatch)
+        let amatch = Atom::Match(MatchRules::init($chars, $vfrom2));
+        Expression::Simple(amatch)
     }};
 }
 
+/// Sequence of expressions
+#[macro_export]
 macro_rules! and {
     ($($e:expr),*) => {{
-        use $crate::parser::expression::{Expression, MultiExpr};
+        use $crate::grammar::{Expression, MultiExpr};
 
         Expression::And(MultiExpr::new(vec![$($e ,)*]))
     }};
 }
 
+/// First expression matching
+#[macro_export]
 macro_rules! or {
     ($($e:expr),*) => {{
-        use $crate::parser::expression::{Expression, MultiExpr};
+        use $crate::grammar::{Expression, MultiExpr};
 
         Expression::Or(MultiExpr::new(vec![$($e ,)*]))
     }};
 }
 
+/// Negation, it doesn't consume  ie: `!expr`
+#[macro_export]
 macro_rules! not {
     ($e:expr) => {{
-        $crate::parser::expression::Expression::Not(Box::new($e))
+        $crate::grammar::Expression::Not(Box::new($e))
     }};
 }
 
+/// Look ahead, it doesn't consume  ie: `&expr`
+#[macro_export]
 macro_rules! peek {
     ($e:expr) => {{
-        $crate::parser::expression::Expression::Peek(Box::new($e))
+        $crate::grammar::Expression::Peek(Box::new($e))
     }};
 }
 
+/// Repetition with minimum and optional maximum
+///
+/// ie: `rep!(lit!("a"), 0)` is `'a'*`, `rep!(lit!("a"), 0, 1)` is `'a'?`
+#[macro_export]
 macro_rules! rep {
     ($e:expr, $min:expr) => {{
-        use $crate::parser::expression;
+        use $crate::grammar::{Expression, RepInfo};
 
-        expression::Expression::Repeat(expression::RepInfo::new(Box::new($e), $min, None))
+        Expression::Repeat(RepInfo::new(Box::new($e), $min, None))
     }};
 
     ($e:expr, $min:expr, $max:expr) => {{
-        use $crate::parser::expression;
+        use $crate::grammar::{Expression, RepInfo};
 
-        expression::Expression::Repeat(expression::RepInfo::new(Box::new($e), $min, Some($max)))
+        Expression::Repeat(RepInfo::new(Box::new($e), $min, Some($max)))
     }};
 }
 
+/// Reference to a rule by name
+#[macro_export]
 macro_rules! ref_rule {
     ($e:expr) => {{
-        $crate::parser::expression::Expression::RuleName($e.to_owned())
+        $crate::grammar::Expression::RuleName($e.to_owned())
     }};
 }
 
+/// Named expression  ie: `named!("n", dot!())` is `n:.`
+#[macro_export]
 macro_rules! named {
     ($name:expr, $mexpr:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         Expression::MetaExpr(MetaExpr::Named(NamedExpr {
             name: $name.to_string(),
             expr: Box::new($mexpr),
@@ -119,9 +141,11 @@ macro_rules! named {
     }};
 }
 
+/// Expression with replace template  ie: `transf2!(and!(...), t2rules!(...))`
+#[macro_export]
 macro_rules! transf2 {
     ($expr:expr, $t2rules:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         Expression::MetaExpr(MetaExpr::Transf2(Transf2Expr {
             mexpr: MultiExpr::new(vec![$expr]),
             transf2_rules: $t2rules,
@@ -129,45 +153,106 @@ macro_rules! transf2 {
     }};
 }
 
+/// Replace template from a list of items (`t2_text!`, `t2_byname!`...)
+#[macro_export]
 macro_rules! t2rules {
     ($($rule:expr),* $(,)*) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         let v = vec![$($rule ,)*];
         ReplTemplate(v)
     }};
 }
 
+/// Template item calling a function  ie: `$(:endl)`
+#[macro_export]
 macro_rules! t2_funct {
     ($e:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         ReplItem::Function($e.to_string())
     }};
 }
 
+/// Template item replaced by name  ie: `$(n)`
+#[macro_export]
 macro_rules! t2_byname {
     ($e:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         ReplItem::ByName($e.to_string())
     }};
 }
 
+/// Template item replaced by name if exists  ie: `$(?n)`
+#[macro_export]
 macro_rules! t2_byname_opt {
     ($e:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         ReplItem::ByNameOpt($e.to_string())
     }};
 }
 
-// macro_rules! t2_bypos {
-//     ($e:expr) => {{
-//         use $crate::parser::expression::*;
-//         ReplItem::ByPos($e)
-//     }};
-// }
+/// Template item with all the occurrences of a name joined  ie: `$(n*, ", ")`
+#[macro_export]
+macro_rules! t2_byname_join {
+    ($e:expr, $sep:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::ByNameJoin($e.to_string(), $sep.to_string())
+    }};
+}
+
+/// Template item with the number of occurrences of a name  ie: `$(#n)`
+#[macro_export]
+macro_rules! t2_byname_count {
+    ($e:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::ByNameCount($e.to_string())
+    }};
+}
+
+/// Template item with an occurrence of a name  ie: `$(n[first])`
+#[macro_export]
+macro_rules! t2_byname_index {
+    ($e:expr, $idx:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::ByNameIndex($e.to_string(), $idx)
+    }};
+}
+
+/// Template conditional on a name  ie: `$(?n: ... | ...)`
+#[macro_export]
+macro_rules! t2_if_named {
+    ($e:expr, $then:expr, $else:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::If(ReplCond::Named($e.to_string()), $then, $else)
+    }};
+}
+
+/// Template conditional on a name value  ie: `$(?n == "-": ... | ...)`
+#[macro_export]
+macro_rules! t2_if_eq {
+    ($e:expr, $lit:expr, $then:expr, $else:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::If(
+            ReplCond::NamedEq($e.to_string(), $lit.to_string()),
+            $then,
+            $else,
+        )
+    }};
+}
+
+/// Template item replaced by possition (1 based)  ie: `$(.1)`
+#[macro_export]
+macro_rules! t2_bypos {
+    ($e:expr) => {{
+        use $crate::grammar::*;
+        ReplItem::ByPos($e)
+    }};
+}
 
+/// Template plain text
+#[macro_export]
 macro_rules! t2_text {
     ($e:expr) => {{
-        use $crate::parser::expression::*;
+        use $crate::grammar::*;
         ReplItem::Text($e.to_string())
     }};
 }
@@ -19,10 +19,38 @@ fn main() -> Result<(), yepp::Error> {
     // println!("{}", result.str());
     // Ok(())
 
-    yepp::process_peg_files_force(&std::path::PathBuf::from("./src"));
+    let src = std::path::PathBuf::from("./src");
+    yepp::process_peg_files_force(&src)?;
+    println!("generated the .peg files on {:?}", src);
+    gen_specialized_peg_parser()?;
+    gen_typed_peg_ast()?;
     main2()
 }
 
+//  specialized parser of peg grammar, to test it against the interpreter
+fn gen_specialized_peg_parser() -> Result<(), yepp::Error> {
+    let code = yepp::Peg::new(&read_file("./src/rules_for_peg.peg")?)
+        .gen_rules()?
+        .specialized_parser()?;
+    write_file("./src/parser/specialized/peg_parser.rs", &code)
+}
+
+//  typed ast of peg grammar, to test the conversion from the ast
+fn gen_typed_peg_ast() -> Result<(), yepp::Error> {
+    let code = yepp::Peg::new(&read_file("./src/rules_for_peg.peg")?)
+        .gen_rules()?
+        .typed_ast()?;
+    write_file("./src/ast/typed/peg_ast.rs", &code)
+}
+
+fn read_file(path: &str) -> Result<String, yepp::Error> {
+    std::fs::read_to_string(path).map_err(|e| yepp::Error::IoErr(path.into(), e))
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), yepp::Error> {
+    std::fs::write(path, content).map_err(|e| yepp::Error::IoErr(path.into(), e))
+}
+
 //  -----------------------------------------------------------------------------------
 //  -----------------------------------------------------------------------------------
 
@@ -112,7 +140,7 @@ fn main2() -> Result<(), yepp::Error> {
 //     )
 //     .gen_rules()?
 //     .parse("aaacbbabdef")?
-//     .replace(Some(&yepp::FnCallBack(custom_funtions)))?
+//     .replace(Some(&mut yepp::FnCallBack(custom_funtions)))?
 //     //  ...
 //     ;
 
@@ -19,7 +19,7 @@ mod test;
 
 /// This is a minimum expression element
 #[derive(Debug, PartialEq)]
-pub(crate) enum Atom {
+pub enum Atom {
     /// Literal string
     Literal(String),
     /// Character matches a list of chars or a list of ranges
@@ -37,7 +37,7 @@ pub(crate) enum Atom {
 /// if char matches one in char slice -> OK
 /// if char matches between tuple in elems slice -> OK
 #[derive(Debug, PartialEq)]
-pub(crate) struct MatchRules(pub(crate) String, pub(crate) Vec<(char, char)>);
+pub struct MatchRules(pub(crate) String, pub(crate) Vec<(char, char)>);
 
 //-----------------------------------------------------------------------
 //-----------------------------------------------------------------------
@@ -49,9 +49,9 @@ pub(crate) struct MatchRules(pub(crate) String, pub(crate) Vec<(char, char)>);
 
 pub(crate) fn parse<'a>(status: Status<'a>, atom: &'a Atom) -> Result<'a> {
     match atom {
-        Atom::Literal(literal) => parse_literal(status, &literal),
-        Atom::Expected(error) => parse_expected(&status, &error),
-        Atom::Match(ref match_rules) => parse_match(status, &match_rules),
+        Atom::Literal(literal) => parse_literal(status, literal),
+        Atom::Expected(error) => parse_expected(&status, error),
+        Atom::Match(ref match_rules) => parse_match(status, match_rules),
         Atom::Dot => parse_dot(status),
         Atom::Eof => parse_eof(status),
     }
@@ -59,7 +59,7 @@ pub(crate) fn parse<'a>(status: Status<'a>, atom: &'a Atom) -> Result<'a> {
 
 impl MatchRules {
     /// Create a MatchRules instance based on string and bounds
-    pub(crate) fn init(s: &str, bounds: Vec<(char, char)>) -> Self {
+    pub fn init(s: &str, bounds: Vec<(char, char)>) -> Self {
         MatchRules(s.to_string(), bounds)
     }
     #[allow(dead_code)] //  used in tests
@@ -85,33 +85,38 @@ impl MatchRules {
 //-----------------------------------------------------------------------
 
 macro_rules! ok {
-    ($st:expr, $val:expr) => {
-        Ok(($st, ast::Node::Val($val.to_owned())))
-    };
+    ($start:expr, $st:expr, $val:expr) => {{
+        let span = ast::Span::new($start, $st.pos.clone());
+        Ok(($st, ast::Node::Val(($val.to_owned(), span))))
+    }};
 }
 
 fn parse_literal<'a>(mut status: Status<'a>, literal: &'a str) -> Result<'a> {
+    let start = status.pos.clone();
     for ch in literal.chars() {
+        let pos = status.pos.clone();
         status = parse_char(status, ch)
-            .map_err(|st| Error::from_status_normal_simple(&st, &literal.to_string()))?;
+            .map_err(|st| Error::from_status_normal_at(&st, &pos, literal))?;
     }
-    ok!(status, literal)
+    ok!(start, status, literal)
 }
 
 fn parse_expected<'a>(status: &Status<'a>, error: &'a str) -> Result<'a> {
     Err(Error::from_status(
-        &status,
-        &ErrorAlternatives::from_string(&error),
+        status,
+        &ErrorAlternatives::from_string(error),
         ErrPriority::Critical,
-    ))
+    )
+    .into())
 }
 
 fn parse_dot(status: Status) -> Result {
+    let start = status.pos.clone();
     let (status, ch) = status
         .get_char()
         .map_err(|st| Error::from_status_normal_simple(&st, "anything"))?;
 
-    ok!(status, ch.to_string())
+    ok!(start, status, ch.to_string())
 }
 
 fn parse_match<'a>(status: Status<'a>, match_rules: &MatchRules) -> Result<'a> {
@@ -128,30 +133,33 @@ fn parse_match<'a>(status: Status<'a>, match_rules: &MatchRules) -> Result<'a> {
         }
     };
 
-    status
-        .get_char()
-        .and_then(|(st, ch)| {
-            if match_char(ch) {
-                ok!(st, ch.to_string())
-            } else {
-                Err(st)
-            }
-        })
-        .map_err(|st| -> Error {
-            Error::from_status_normal_simple(
-                &st,
-                &format!("match {} {:?}", match_rules.0, match_rules.1),
-            )
-        })
+    let start = status.pos.clone();
+    let error = |st: &Status| -> Box<Error> {
+        Error::from_status_normal_at(
+            st,
+            &start,
+            &format!("match {} {:?}", match_rules.0, match_rules.1),
+        )
+        .into()
+    };
+    let (st, ch) = status.get_char().map_err(|st| error(&st))?;
+    if match_char(ch) {
+        ok!(start.clone(), st, ch.to_string())
+    } else {
+        Err(error(&st))
+    }
 }
 
 fn parse_eof(status: Status) -> Result {
+    let start = status.pos.clone();
     match status.get_char() {
-        Ok((st, _ch)) => Err(Error::from_status_normal_simple(&st, "expected EOF")),
-        Err(st) => ok!(st, "EOF"),
+        Ok((st, _ch)) => Err(Error::from_status_normal_at(&st, &start, "expected EOF").into()),
+        Err(st) => ok!(start, st, "EOF"),
     }
 }
 
+//  the status is given back when the char doesn't match
+#[allow(clippy::result_large_err)]
 fn parse_char(status: Status, ch: char) -> result::Result<Status, Status> {
     let (st, got_ch) = status.get_char()?;
     if ch == got_ch {
@@ -162,24 +170,13 @@ fn parse_char(status: Status, ch: char) -> result::Result<Status, Status> {
 }
 
 impl<'a> Status<'a> {
+    //  the status is given back at the end of the text
+    #[allow(clippy::result_large_err)]
     fn get_char(mut self) -> result::Result<(Self, char), Self> {
         match self.it_parsing.next() {
             None => Err(self),
             Some(ch) => {
-                self.pos.n += 1;
-                match ch {
-                    '\n' => {
-                        self.pos.col = 0;
-                        self.pos.row += 1;
-                        self.pos.start_line = self.pos.n;
-                    }
-                    '\r' => {
-                        self.pos.col = 0;
-                    }
-                    _ => {
-                        self.pos.col += 1;
-                    }
-                }
+                self.pos.advance(ch);
                 Ok((self, ch))
             }
         }
@@ -144,3 +144,47 @@ fn test_parse_match_eof_error() {
 
     assert!(parse_eof(status).is_err());
 }
+
+#[test]
+fn test_parse_literal_utf8() {
+    let rules = rules!{};
+    let status_init = Status::init("añoñ 漢字", &rules);
+    let (status_end, _) = parse_literal(status_init, "añoñ 漢").ok().unwrap();
+
+    assert_eq!(status_end.pos.n, "añoñ 漢".len());
+    assert_eq!(status_end.pos.n_char, 6);
+    assert_eq!(status_end.pos.col, 6);
+    assert_eq!(status_end.pos.col_utf16, 6);
+    assert_eq!(status_end.pos.row, 0);
+}
+
+#[test]
+fn test_parse_dot_utf16_col() {
+    let rules = rules!{};
+    let status = Status::init("😀\nñ😀a", &rules);
+    let (status, _) = parse_dot(status).ok().unwrap();
+    assert_eq!(status.pos.n, 4);
+    assert_eq!(status.pos.col, 1);
+    assert_eq!(status.pos.col_utf16, 2);
+
+    let (status, _) = parse_dot(status).ok().unwrap();
+    let (status, _) = parse_dot(status).ok().unwrap();
+    let (status, _) = parse_dot(status).ok().unwrap();
+    assert_eq!(status.pos.row, 1);
+    assert_eq!(status.pos.start_line, 5);
+    assert_eq!(status.pos.n, 11);
+    assert_eq!(status.pos.n_char, 4);
+    assert_eq!(status.pos.col, 2);
+    assert_eq!(status.pos.col_utf16, 3);
+}
+
+#[test]
+fn test_parse_literal_fail_utf8() {
+    let rules = rules!{};
+    let status_init = Status::init("ñandú", &rules);
+    let error = parse_literal(status_init, "ñanda").err().unwrap();
+
+    assert_eq!(error.pos.n, "ñand".len());
+    assert_eq!(error.line_before, "ñand");
+    assert_eq!(error.line_after, "ú");
+}
@@ -1,14 +1,17 @@
 #![warn(missing_docs)]
 //! Here we have the parser for non atomic things
 
-use super::super::idata::{
-    cont::IVec,
-    tc::{tail_call, TailCall},
-};
+use super::super::idata::cont::IVec;
 use crate::ast;
-use crate::parser::{atom, atom::Atom, ErrPriority, Error, Result, Status};
-use std::collections::HashMap;
+use crate::parser::{
+    atom, atom::Atom, ErrPriority, Error, ErrorAlternatives, LeftRec, Memo, MemoEntry, Possition,
+    Result, RuleCall, Status,
+};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::result;
+use std::str::Chars;
 
 #[cfg(test)]
 mod test;
@@ -21,70 +24,106 @@ mod test;
 //-----------------------------------------------------------------------
 //-----------------------------------------------------------------------
 
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct Started(usize);
-
-pub(crate) type ResultExpr<'a> = result::Result<(Status<'a>, Vec<ast::Node>), Error>;
+pub(crate) type ResultExpr<'a> = result::Result<(Status<'a>, Vec<ast::Node>), Box<Error>>;
 
+/// A rule, the expression and an optional description
 #[derive(Debug, PartialEq)]
-pub(crate) struct RuleInfo {
-    pub(crate) expr: Expression,
-    pub(crate) descr: Option<String>,
+pub struct RuleInfo {
+    /// expression to parse the rule
+    pub expr: Expression,
+    /// description of the rule
+    pub descr: Option<String>,
+}
+
+impl RuleInfo {
+    /// rule without description
+    pub fn new(expr: Expression) -> Self {
+        RuleInfo { expr, descr: None }
+    }
 }
 
 /// The set of rules to be parsed
 /// Any rule has a name
 /// A rule can be registered just once
 /// The starting rule is main
+/// The rules keep the order they were added
+///
+/// To write it by hand, use the macros (`rules!`, `and!`, `lit!`...)
+/// or the builder
+///
+/// ```rust
+/// use yepp::{and, lit, or, ref_rule, rep};
+/// use yepp::grammar::{RuleInfo, SetOfRules};
+///
+/// let rules = SetOfRules::empty()
+///     .add("main", RuleInfo::new(rep!(ref_rule!("ab"), 1)))
+///     .add("ab", RuleInfo::new(or!(and!(lit!("a")), and!(lit!("b")))));
+///
+/// assert!(rules.parse("abba").is_ok());
+/// ```
 #[derive(Debug, PartialEq)]
-pub struct SetOfRules(pub(crate) HashMap<String, RuleInfo>);
+pub struct SetOfRules(pub(crate) IndexMap<String, RuleInfo>);
 
 impl SetOfRules {
-    /// Initialize a set of rules with a hashmap of <String, RuleInfo>
+    /// Initialize a set of rules with a map of <String, RuleInfo>
     /// In general, is better to use the ```rules!``` macro
-    pub(crate) fn new(mrules: HashMap<String, RuleInfo>) -> Self {
+    pub(crate) fn new(mrules: IndexMap<String, RuleInfo>) -> Self {
         SetOfRules(mrules)
     }
 
     /// return a set of rules empty
-    pub(crate) fn empty() -> Self {
-        SetOfRules::new(HashMap::<String, RuleInfo>::new())
+    pub fn empty() -> Self {
+        SetOfRules::new(IndexMap::<String, RuleInfo>::new())
     }
 
-    pub(crate) fn add(mut self, name: &str, ri: RuleInfo) -> Self {
+    /// add a rule, replacing it if it was defined
+    pub fn add(mut self, name: &str, ri: RuleInfo) -> Self {
         self.0.insert(name.to_owned(), ri);
         self
     }
 
-    pub(crate) fn merge(self, rules2merge: Self) -> Self {
-        SetOfRules(rules2merge.0.into_iter().chain(self.0).collect())
+    /// add the rules not already defined, after the current ones
+    pub(crate) fn merge(mut self, rules2merge: Self) -> Self {
+        for (name, ri) in rules2merge.0 {
+            self.0.entry(name).or_insert(ri);
+        }
+        self
     }
-}
 
-#[allow(missing_docs)]
-#[derive(Debug, Clone)]
-pub(crate) struct Named(pub(crate) String);
+    /// move `main` to the first possition
+    pub(crate) fn main_first(mut self) -> Self {
+        if let Some(idx) = self.0.get_index_of("main") {
+            self.0.move_index(idx, 0);
+        }
+        self
+    }
+}
 
-#[allow(missing_docs)]
+/// Expression with a name to reference it on replace templates
 #[derive(Debug, PartialEq)]
-pub(crate) struct NamedExpr {
-    pub(crate) name: String,
-    pub(crate) expr: Box<Expression>,
+pub struct NamedExpr {
+    /// the name
+    pub name: String,
+    /// the named expression
+    pub expr: Box<Expression>,
 }
 
-#[allow(missing_docs)]
+/// Expression with a replace template
 #[derive(Debug, PartialEq)]
-pub(crate) struct Transf2Expr {
-    pub(crate) mexpr: MultiExpr,
-    pub(crate) transf2_rules: ReplTemplate,
+pub struct Transf2Expr {
+    /// the expression
+    pub mexpr: MultiExpr,
+    /// the replace template
+    pub transf2_rules: ReplTemplate,
 }
 
 /// Replace item options
 #[derive(Debug, PartialEq, Clone)]
-pub(crate) enum ReplItem {
+pub enum ReplItem {
     /// write plain text
     Text(String),
-    /// replace from possition  ie: $(.1)
+    /// replace from possition (1 based) ie: $(.1)
+    /// counting the rules and named elements, not the literals
     ByPos(usize),
     /// replace by name ie: $(name)
     ByName(String),
@@ -92,11 +131,77 @@ pub(crate) enum ReplItem {
     Function(String),
     /// replace by name if exits ie: $(name)
     ByNameOpt(String),
+    /// all the occurrences of a name joined with a separator  ie: $(name*, ", ")
+    ByNameJoin(String, String),
+    /// number of occurrences of a name  ie: $(#name)
+    ByNameCount(String),
+    /// an occurrence of a name  ie: $(name[first]) $(name[last]) $(name[2])
+    ByNameIndex(String, NameIndex),
+    /// write the first template if the condition is true, the second otherwise
+    /// ie: $(?sign: NEG | POS)
+    If(ReplCond, ReplTemplate, ReplTemplate),
+}
+
+/// Condition on a replace template
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReplCond {
+    /// the name exists  ie: $(?sign: ...)
+    Named(String),
+    /// the replaced text of name is equal to literal  ie: $(?sign == "-": ...)
+    NamedEq(String, String),
+}
+
+impl std::fmt::Display for ReplTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.iter().try_for_each(|item| write!(f, "{}", item))
+    }
+}
+
+impl std::fmt::Display for ReplItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ReplItem::Text(t) => write!(f, "{}", t),
+            ReplItem::ByPos(p) => write!(f, "$(.{})", p),
+            ReplItem::ByName(n) => write!(f, "$({})", n),
+            ReplItem::Function(fn_txt) => write!(f, "$(:{})", fn_txt),
+            ReplItem::ByNameOpt(n) => write!(f, "$(?{})", n),
+            ReplItem::ByNameJoin(n, sep) => write!(f, "$({}*, {:?})", n, sep),
+            ReplItem::ByNameCount(n) => write!(f, "$(#{})", n),
+            ReplItem::ByNameIndex(n, idx) => write!(f, "$({}[{}])", n, idx),
+            ReplItem::If(ReplCond::Named(n), then, else_) => {
+                write!(f, "$(?{}: {}| {})", n, then, else_)
+            }
+            ReplItem::If(ReplCond::NamedEq(n, lit), then, else_) => {
+                write!(f, "$(?{} == {:?}: {}| {})", n, lit, then, else_)
+            }
+        }
+    }
+}
+
+/// Which occurrence of a repeated name
+#[derive(Debug, PartialEq, Clone)]
+pub enum NameIndex {
+    /// first occurrence
+    First,
+    /// last occurrence
+    Last,
+    /// 1 based
+    Nth(usize),
+}
+
+impl std::fmt::Display for NameIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NameIndex::First => write!(f, "first"),
+            NameIndex::Last => write!(f, "last"),
+            NameIndex::Nth(n) => write!(f, "{}", n),
+        }
+    }
 }
 
 /// template to apply the replaces
 #[derive(Debug, PartialEq, Clone)]
-pub(crate) struct ReplTemplate(pub(crate) Vec<ReplItem>);
+pub struct ReplTemplate(pub Vec<ReplItem>);
 
 impl ReplTemplate {
     pub(crate) fn empty() -> Self {
@@ -109,33 +214,43 @@ impl ReplTemplate {
     }
 }
 
-#[allow(missing_docs)]
+/// Expressions with extra information
 #[derive(Debug, PartialEq)]
-pub(crate) enum MetaExpr {
+pub enum MetaExpr {
+    /// named expression  ie: `n:expr`
     Named(NamedExpr),
+    /// expression with replace template  ie: `expr -> template`
     Transf2(Transf2Expr),
 }
 
-#[allow(missing_docs)]
+/// Parsing expression
 #[derive(Debug, PartialEq)]
-pub(crate) enum Expression {
+pub enum Expression {
+    /// atomic expression (literal, match, dot...)
     Simple(Atom),
+    /// all the expressions in sequence
     And(MultiExpr),
+    /// the first expression matching
     Or(MultiExpr),
+    /// succeed if the expression doesn't match, consuming nothing  ie: `!expr`
     Not(Box<Expression>),
+    /// succeed if the expression matches, consuming nothing  ie: `&expr`
     Peek(Box<Expression>),
+    /// repetition  ie: `expr*`
     Repeat(RepInfo),
+    /// reference to a rule
     RuleName(String),
+    /// named or with replace template
     MetaExpr(MetaExpr),
 }
 
 /// Opaque type to manage multiple expressions
 #[derive(Debug, PartialEq)]
-pub(crate) struct MultiExpr(pub(crate) Vec<Expression>);
+pub struct MultiExpr(pub(crate) Vec<Expression>);
 
 impl MultiExpr {
     /// Creates a new instance of ```MultiExpr``` from a vector
-    pub(crate) fn new(v: Vec<Expression>) -> Self {
+    pub fn new(v: Vec<Expression>) -> Self {
         MultiExpr(v)
     }
 
@@ -148,7 +263,7 @@ impl MultiExpr {
 
 /// Opaque type to manage repetition subexpression
 #[derive(Debug, PartialEq)]
-pub(crate) struct RepInfo {
+pub struct RepInfo {
     pub(crate) expression: Box<Expression>,
     pub(crate) min: NRep,
     pub(crate) max: Option<NRep>,
@@ -157,7 +272,7 @@ pub(crate) struct RepInfo {
 impl RepInfo {
     /// Creates a Repeticion Info for an expression with min and
     /// optionally max values to repeat
-    pub(crate) fn new(expression: Box<Expression>, min: usize, max: Option<usize>) -> Self {
+    pub fn new(expression: Box<Expression>, min: usize, max: Option<usize>) -> Self {
         RepInfo {
             expression,
             min: NRep(min),
@@ -179,8 +294,8 @@ pub(crate) struct NRep(pub(crate) usize);
 //-----------------------------------------------------------------------
 
 //-----------------------------------------------------------------------
-pub(crate) fn parse(status: Status) -> Result {
-    parse_rule_name(status, "main")
+pub(crate) fn parse<'a>(status: Status<'a>, start_rule: &str) -> Result<'a> {
+    parse_rule_name(status, start_rule)
 }
 
 //-----------------------------------------------------------------------
@@ -196,19 +311,10 @@ fn parse_rule_name<'a>(status: Status<'a>, rule_name: &str) -> Result<'a> {
         status
     };
 
-    let rules = &status.rules.0;
-    let rule_info = rules.get(rule_name).ok_or_else(|| {
-        Error::from_status_simple(
-            &status,
-            &format!("Missing rule: {}", rule_name),
-            ErrPriority::Critical,
-        )
-    })?;
-    let (st, nodes) =
-        parse_expr(status, &rule_info.expr).map_err(|err| match &rule_info.descr {
-            Some(d) => err.with_context(d),
-            None => err.with_context(""),
-        })?;
+    let (name, rule_info) = match status.rules.0.get_key_value(rule_name) {
+        Some(rule) => rule,
+        None => return Err(missing_rule(&status, rule_name).into()),
+    };
 
     // let elapsed = start.elapsed();
     // println!(
@@ -217,7 +323,184 @@ fn parse_rule_name<'a>(status: Status<'a>, rule_name: &str) -> Result<'a> {
     //     elapsed.as_secs(),
     //     elapsed.subsec_millis()
     // );
-    Ok((st, ast::Node::Rule((rule_name.to_owned(), nodes))))
+    match reuse_entry(&status, name) {
+        Some(entry) => entry.map(|entry| entry.apply(status)),
+        None => parse_rule_left_rec(status, name, rule_info),
+    }
+}
+
+#[cold]
+fn missing_rule(status: &Status, rule_name: &str) -> Error {
+    Error::from_status_simple(
+        status,
+        &format!("Missing rule: {}", rule_name),
+        ErrPriority::Critical,
+    )
+}
+
+//  memoized result, seed of a left recursion being grown or the cut of a
+//  new left recursion
+//  `None` if the rule has to be parsed
+//  out of line, to keep small the frames of the recursive path
+#[inline(never)]
+fn reuse_entry<'a>(
+    status: &Status<'a>,
+    rule_name: &'a str,
+) -> Option<result::Result<MemoEntry<'a>, Box<Error>>> {
+    let key = (rule_name, status.pos.n);
+    let cached = match &status.memo {
+        Some(memo) => memo.borrow().get(&key).cloned().map(|entry| match entry {
+            Ok(entry) => Ok(entry.on_path(&status.walking_rules)),
+            Err(e) => Err(e.on_path(&status.walking_rules)),
+        }),
+        None => None,
+    };
+    let seed = || status.left_rec.borrow().seeds.get(&key).cloned().map(Ok);
+
+    match cached.or_else(seed) {
+        Some(entry) => Some(entry),
+        None if status
+            .left_rec
+            .borrow_mut()
+            .check_recursion(rule_name, key.1) =>
+        {
+            //  cut the recursion, the seed has to come from another alternative
+            Some(Err(Error::from_status(
+                status,
+                &ErrorAlternatives::empty(),
+                ErrPriority::Normal,
+            )
+            .into()))
+        }
+        None => None,
+    }
+}
+
+fn parse_rule_info<'a>(status: Status<'a>, rule_name: &str, rule_info: &'a RuleInfo) -> Result<'a> {
+    let start = status.pos.clone();
+    parse_expr(status, &rule_info.expr)
+        .map(|(st, nodes)| {
+            let span = ast::Span::new(start, st.pos.clone());
+            (st, ast::Node::Rule((rule_name.to_owned(), nodes, span)))
+        })
+        .map_err(|err| err.with_context(rule_info.descr.as_deref().unwrap_or("")))
+}
+
+//  where and how a rule started to be parsed
+struct RuleStart<'a> {
+    name: &'a str,
+    pos: Possition,
+    it_parsing: Chars<'a>,
+    walking_rules: usize,
+    //  the one before the rule, it's restored if the rule doesn't produce one
+    potential_error: Option<Box<Error>>,
+    memo: Option<Rc<RefCell<Memo<'a>>>>,
+    left_rec: Rc<RefCell<LeftRec<'a>>>,
+}
+
+impl<'a> RuleStart<'a> {
+    //  the potential error is cleaned before parsing the rule, in order to
+    //  keep just the one produced inside the rule
+    fn take(status: &mut Status<'a>, name: &'a str) -> Self {
+        RuleStart {
+            name,
+            pos: status.pos.clone(),
+            it_parsing: status.it_parsing.clone(),
+            walking_rules: status.walking_rules.len(),
+            potential_error: status.potential_error.take(),
+            memo: status.memo.clone(),
+            left_rec: status.left_rec.clone(),
+        }
+    }
+
+    //  status to parse the rule again
+    fn restart(&self, mut status: Status<'a>) -> Status<'a> {
+        status.pos = self.pos.clone();
+        status.it_parsing = self.it_parsing.clone();
+        status.walking_rules.truncate(self.walking_rules);
+        status.potential_error = None;
+        status
+    }
+}
+
+//  if the rule called itself on the same possition, the result is the seed
+//  to grow the left recursion
+fn parse_rule_left_rec<'a>(
+    mut status: Status<'a>,
+    rule_name: &'a str,
+    rule_info: &'a RuleInfo,
+) -> Result<'a> {
+    let start = RuleStart::take(&mut status, rule_name);
+    start.left_rec.borrow_mut().calls.push(RuleCall {
+        rule: rule_name,
+        pos: start.pos.n,
+        left_rec: false,
+    });
+    let result = parse_rule_info(status, rule_name, rule_info);
+    end_rule(start, rule_info, result)
+}
+
+//  results are memoized (packrat) if they don't depend on a seed being grown
+//  out of line, to keep small the frames of the recursive path
+#[inline(never)]
+fn end_rule<'a>(start: RuleStart<'a>, rule_info: &'a RuleInfo, result: Result<'a>) -> Result<'a> {
+    let call = start.left_rec.borrow_mut().calls.pop();
+
+    let result = match (call, result) {
+        (Some(RuleCall { left_rec: true, .. }), Ok(seed)) => grow_seed(&start, rule_info, seed),
+        //  nothing but the cut recursion, there is no alternative to start
+        (Some(RuleCall { left_rec: true, .. }), Err(e))
+            if e.priority == ErrPriority::Normal && e.alternatives.expected.is_empty() =>
+        {
+            Err(Box::new(Error {
+                alternatives: ErrorAlternatives::from_string(&format!(
+                    "left recursion on {}",
+                    start.name
+                )),
+                priority: ErrPriority::Critical,
+                ..*e
+            }))
+        }
+        (_, result) => result,
+    };
+
+    if let Some(memo) = &start.memo {
+        if !start.left_rec.borrow().growing_on(start.pos.n) {
+            let entry = match &result {
+                Ok(result) => Ok(MemoEntry::from(result).inner_path(start.walking_rules)),
+                Err(e) => Err(e.clone().inner_path(start.walking_rules)),
+            };
+            memo.borrow_mut().insert((start.name, start.pos.n), entry);
+        }
+    }
+    result.map(|(mut st, node)| {
+        if st.potential_error.is_none() {
+            st.potential_error = start.potential_error;
+        }
+        (st, node)
+    })
+}
+
+//  parse again the rule using the seed for the recursive call
+//  while it consumes more input
+fn grow_seed<'a>(
+    start: &RuleStart<'a>,
+    rule_info: &'a RuleInfo,
+    seed: (Status<'a>, ast::Node),
+) -> Result<'a> {
+    let key = (start.name, start.pos.n);
+    let mut seed = seed;
+    let result = loop {
+        let entry = MemoEntry::from(&seed);
+        start.left_rec.borrow_mut().seeds.insert(key, entry);
+        match parse_rule_info(start.restart(seed.0.clone()), start.name, rule_info) {
+            Ok(grown) if grown.0.pos.n > seed.0.pos.n => seed = grown,
+            Err(e) if e.priority == ErrPriority::Critical => break Err(e),
+            _ => break Ok(seed),
+        }
+    };
+    start.left_rec.borrow_mut().seeds.remove(&key);
+    result
 }
 
 fn parse_atom_as_expr<'a>(status: Status<'a>, a: &'a Atom) -> ResultExpr<'a> {
@@ -226,105 +509,97 @@ fn parse_atom_as_expr<'a>(status: Status<'a>, a: &'a Atom) -> ResultExpr<'a> {
 }
 
 fn parse_rule_name_as_expr<'a>(status: Status<'a>, rule_name: &str) -> ResultExpr<'a> {
-    let (st, ast) = parse_rule_name(status, rule_name)?;
-    Ok((st, vec![ast]))
+    parse_rule_name(status, rule_name).map(|(st, ast)| (st, vec![ast]))
 }
 
 fn parse_metaexpr<'a>(status: Status<'a>, meta_expr: &'a MetaExpr) -> ResultExpr<'a> {
     match meta_expr {
-        MetaExpr::Named(NamedExpr { name, expr }) => {
-            let (status, nodes) = parse_expr(status, expr)?;
-            Ok((status, vec![ast::Node::Named((name.to_string(), nodes))]))
-        }
-        MetaExpr::Transf2(Transf2Expr {
-            mexpr,
-            transf2_rules,
-        }) => {
-            let (status, nodes) = parse_and(status, mexpr)?;
-            Ok((
-                status,
-                vec![ast::Node::Transf2(ast::Transf2 {
-                    template: transf2_rules.clone(),
-                    nodes,
-                })],
-            ))
-        }
+        MetaExpr::Named(named) => parse_named(status, named),
+        MetaExpr::Transf2(transf2) => parse_transf2(status, transf2),
     }
 }
 
+fn parse_named<'a>(status: Status<'a>, named: &'a NamedExpr) -> ResultExpr<'a> {
+    let start = status.pos.clone();
+    parse_expr(status, &named.expr).map(|(st, nodes)| {
+        let span = ast::Span::new(start, st.pos.clone());
+        let node = ast::Node::Named((named.name.to_string(), nodes, span));
+        (st, vec![node])
+    })
+}
+
+fn parse_transf2<'a>(status: Status<'a>, transf2: &'a Transf2Expr) -> ResultExpr<'a> {
+    let start = status.pos.clone();
+    parse_and(status, &transf2.mexpr).map(|(st, nodes)| {
+        let span = ast::Span::new(start, st.pos.clone());
+        let node = ast::Node::Transf2(ast::Transf2 {
+            template: transf2.transf2_rules.clone(),
+            nodes,
+            span,
+        });
+        (st, vec![node])
+    })
+}
+
 fn parse_expr<'a>(status: Status<'a>, expression: &'a Expression) -> ResultExpr<'a> {
     match *expression {
-        Expression::Simple(ref val) => parse_atom_as_expr(status, &val),
-        Expression::And(ref val) => parse_and(status, &val),
-        Expression::Or(ref val) => parse_or(&status, &val),
-        Expression::Not(ref val) => parse_not(status, &val),
-        Expression::Peek(ref val) => parse_peek(status, &val),
-        Expression::Repeat(ref val) => parse_repeat(status, &val),
-        Expression::RuleName(ref val) => parse_rule_name_as_expr(status, &val),
-        Expression::MetaExpr(ref val) => parse_metaexpr(status, &val),
+        Expression::Simple(ref val) => parse_atom_as_expr(status, val),
+        Expression::And(ref val) => parse_and(status, val),
+        Expression::Or(ref val) => parse_or(&status, val),
+        Expression::Not(ref val) => parse_not(status, val),
+        Expression::Peek(ref val) => parse_peek(status, val),
+        Expression::Repeat(ref val) => parse_repeat(status, val),
+        Expression::RuleName(ref val) => parse_rule_name_as_expr(status, val),
+        Expression::MetaExpr(ref val) => parse_metaexpr(status, val),
     }
 }
 
 //-----------------------------------------------------------------------
 fn parse_and<'a>(status: Status<'a>, multi_expr: &'a MultiExpr) -> ResultExpr<'a> {
-    let init_tc: (_, &[Expression], Vec<ast::Node>) = (status, &(multi_expr.0), vec![]);
-
-    tail_call(init_tc, |acc| {
-        if acc.1.is_empty() {
-            TailCall::Return(Ok((acc.0, acc.2)))
-        } else {
-            let result_parse = parse_expr(acc.0, &acc.1[0]);
-            match result_parse {
-                Ok((status, vnodes)) => {
-                    TailCall::Call((status, &acc.1[1..], acc.2.iappend(vnodes)))
-                }
-                Err(err) => TailCall::Return(Err(err)),
-            }
-        }
-    })
+    //  a loop, not a tail call, to keep small the frames of the recursive path
+    let mut status = status;
+    let mut nodes = vec![];
+    for expr in &multi_expr.0 {
+        let (st, vnodes) = parse_expr(status, expr)?;
+        status = st;
+        nodes = nodes.iappend(vnodes);
+    }
+    Ok((status, nodes))
 }
 
 //-----------------------------------------------------------------------
 fn parse_or<'a>(status: &Status<'a>, multi_expr: &'a MultiExpr) -> ResultExpr<'a> {
-    let merge_errors = |oe1: Option<Error>, e2: Error| match oe1 {
+    let merge_errors = |oe1: Option<Box<Error>>, e2: Box<Error>| match oe1 {
         Some(e1) => match e1.priority > e2.priority {
             true => Some(e1),
-            false => Some(e1.merge(&e2)),
+            false => Some(Box::new(e1.merge(&e2))),
         },
         None => Some(e2),
     };
 
-    let init_tc: (_, &[Expression], Option<Error>) = (status.clone(), &(multi_expr.0), None);
-
-    tail_call(init_tc, |acc| {
-        if acc.1.is_empty() {
-            TailCall::Return(Err(match acc.2 {
-                Some(err) => err,
-                _ => Error::from_status_normal_simple(
-                    &status,
-                    "LOGIC ERROR!!! checked all options in or with ¿NO? errors",
-                ),
-            }))
-        } else {
-            let try_parse = parse_expr(acc.0.clone(), &acc.1[0]);
-            match try_parse {
-                Ok(result) => TailCall::Return(Ok(result)),
-                Err(e) => {
-                    if e.priority == ErrPriority::Critical {
-                        TailCall::Return(Err(e))
-                    } else {
-                        TailCall::Call((acc.0, &acc.1[1..], merge_errors(acc.2, e)))
-                    }
-                }
-            }
+    //  a loop, not a tail call, to keep small the frames of the recursive path
+    let mut error = None;
+    for expr in &multi_expr.0 {
+        match parse_expr(status.clone(), expr) {
+            Ok(result) => return Ok(result),
+            Err(e) if e.priority == ErrPriority::Critical => return Err(e),
+            Err(e) => error = merge_errors(error, e),
         }
+    }
+    Err(match error {
+        Some(err) => err,
+        _ => Error::from_status_normal_simple(
+            status,
+            "LOGIC ERROR!!! checked all options in or with ¿NO? errors",
+        )
+        .into(),
     })
 }
 
 //-----------------------------------------------------------------------
 fn parse_not<'a>(status: Status<'a>, expression: &'a Expression) -> ResultExpr<'a> {
     match parse_expr(status.clone(), expression) {
-        Ok(_) => Err(Error::from_status_normal_simple(&status, "not")),
+        Ok(_) => Err(Error::from_status_normal_simple(&status, "not").into()),
         Err(_) => Ok((status, vec![])),
     }
 }
@@ -332,7 +607,7 @@ fn parse_not<'a>(status: Status<'a>, expression: &'a Expression) -> ResultExpr<'
 fn parse_peek<'a>(status: Status<'a>, expression: &'a Expression) -> ResultExpr<'a> {
     match parse_expr(status.clone(), expression) {
         Ok(_) => Ok((status, vec![])),
-        Err(_) => Err(Error::from_status_normal_simple(&status, "not")),
+        Err(_) => Err(Error::from_status_normal_simple(&status, "not").into()),
     }
 }
 
@@ -344,30 +619,28 @@ fn parse_repeat<'a>(status: Status<'a>, rep_info: &'a RepInfo) -> ResultExpr<'a>
         None => false,
     };
 
-    let init_tc: (_, _, Vec<ast::Node>) = (status, 0, vec![]);
-    tail_call(init_tc, |acc| {
-        let try_parse = parse_expr(acc.0.clone(), &rep_info.expression);
-        match (try_parse, big_min_bound(acc.1), touch_max_bound(acc.1)) {
-            (Err(e), true, _) => {
-                if e.priority == ErrPriority::Critical {
-                    TailCall::Return(Err(e))
-                } else {
-                    TailCall::Return(Ok((acc.0.set_potential_error(e), acc.2)))
+    //  a loop, not a tail call, to keep small the frames of the recursive path
+    let mut status = status;
+    let mut nodes = vec![];
+    let mut counter = 0;
+    loop {
+        match parse_expr(status.clone(), &rep_info.expression) {
+            Ok((st, vnodes)) => {
+                //  no progress, repeating again would loop forever
+                let end = touch_max_bound(counter) || st.pos.n == status.pos.n;
+                status = st;
+                nodes = nodes.iappend(vnodes);
+                if end {
+                    return Ok((status, nodes));
                 }
+                counter += 1;
             }
-            (Err(e), false, _) => TailCall::Return(Err(e)),
-            //     Err(Error::from_status(
-            //     &acc.0,
-            //     &format!("inside repeat {:#?}", e),
-            // ))),
-            (Ok((status, vnodes)), _, false) => {
-                TailCall::Call((status, acc.1 + 1, acc.2.iappend(vnodes)))
-            }
-            (Ok((status, vnodes)), _, true) => {
-                TailCall::Return(Ok((status, acc.2.iappend(vnodes))))
+            Err(e) if e.priority == ErrPriority::Normal && big_min_bound(counter) => {
+                return Ok((status.set_potential_error(e), nodes))
             }
+            Err(e) => return Err(e),
         }
-    })
+    }
 }
 //  SUPPORT
 //-----------------------------------------------------------------------
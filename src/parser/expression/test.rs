@@ -218,3 +218,21 @@ fn test_parse_repeat_fail() {
         assert!(parse_expr(status_init, &expr).is_err());
     }
 }
+
+#[test]
+fn test_parse_repeat_nullable() {
+    let rules = rules! {};
+    let expr = Expression::Repeat(RepInfo {
+        expression: Box::new(Expression::Repeat(RepInfo {
+            expression: Box::new(Expression::Simple(Atom::Literal("a".to_string()))),
+            min: NRep(0),
+            max: None,
+        })),
+        min: NRep(0),
+        max: None,
+    });
+
+    let status_init = Status::init("aaab", &rules);
+    let (status, _) = parse_expr(status_init, &expr).ok().unwrap();
+    assert_eq!(status.pos.n, 3);
+}
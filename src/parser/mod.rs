@@ -17,7 +17,11 @@ use std::result;
 /// Support for minimum expressions elements
 pub(crate) mod atom;
 pub(crate) mod expression;
+pub mod specialized;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::str::Chars;
 
 //-----------------------------------------------------------------------
@@ -30,14 +34,18 @@ use std::str::Chars;
 
 /// Information about the possition on parsing
 #[derive(PartialEq, Clone, Debug)]
-pub(crate) struct Possition {
-    /// char position parsing
+pub struct Possition {
+    /// byte position parsing (offset on text to parse)
     pub(crate) n: usize,
+    /// char position parsing
+    pub(crate) n_char: usize,
     /// row parsing row
     pub(crate) row: usize,
-    /// parsing col
+    /// parsing col (in chars)
     pub(crate) col: usize,
-    /// possition were line started for current pos *m*
+    /// parsing col in UTF-16 code units (as editors usually count)
+    pub(crate) col_utf16: usize,
+    /// byte possition were line started for current pos *n*
     pub(crate) start_line: usize,
 }
 
@@ -45,16 +53,40 @@ impl Possition {
     fn init() -> Self {
         Self {
             n: 0,
+            n_char: 0,
             row: 0,
             col: 0,
+            col_utf16: 0,
             start_line: 0,
         }
     }
+
+    /// move after `ch`
+    pub(crate) fn advance(&mut self, ch: char) {
+        self.n += ch.len_utf8();
+        self.n_char += 1;
+        match ch {
+            '\n' => {
+                self.col = 0;
+                self.col_utf16 = 0;
+                self.row += 1;
+                self.start_line = self.n;
+            }
+            '\r' => {
+                self.col = 0;
+                self.col_utf16 = 0;
+            }
+            _ => {
+                self.col += 1;
+                self.col_utf16 += ch.len_utf16();
+            }
+        }
+    }
 }
 
 /// Error priority
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-pub(crate) enum ErrPriority {
+pub enum ErrPriority {
     /// normal error
     Normal,
     /// Very important error
@@ -62,7 +94,7 @@ pub(crate) enum ErrPriority {
 }
 
 /// Context error information
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Error {
     /// Possition achive parsing
     pub(crate) pos: Possition,
@@ -83,7 +115,8 @@ pub struct Error {
     pub(crate) priority: ErrPriority,
 }
 
-#[derive(Debug, Clone)]
+/// What was expected on the error possition
+#[derive(Debug, PartialEq, Clone)]
 pub struct ErrorAlternatives {
     pub(crate) context: Option<String>,
     pub(crate) expected: Vec<String>,
@@ -96,6 +129,112 @@ impl ErrorAlternatives {
             expected: vec![s.to_owned()],
         }
     }
+    /// nothing expected, it doesn't add information when merged
+    pub(crate) fn empty() -> Self {
+        ErrorAlternatives {
+            context: None,
+            expected: vec![],
+        }
+    }
+}
+
+//-----------------------------------------------------------------------
+/// Result of parsing a rule on a possition, kept to be reused (packrat)
+#[derive(Debug, Clone)]
+pub(crate) struct MemoEntry<'a> {
+    pub(crate) pos: Possition,
+    pub(crate) it_parsing: Chars<'a>,
+    /// potential error produced inside the rule (if any)
+    pub(crate) potential_error: Option<Box<Error>>,
+    pub(crate) node: ast::Node,
+}
+
+impl<'a> From<&(Status<'a>, ast::Node)> for MemoEntry<'a> {
+    fn from((status, node): &(Status<'a>, ast::Node)) -> Self {
+        MemoEntry {
+            pos: status.pos.clone(),
+            it_parsing: status.it_parsing.clone(),
+            potential_error: status.potential_error.clone(),
+            node: node.clone(),
+        }
+    }
+}
+
+impl<'a> MemoEntry<'a> {
+    /// continue parsing after the rule
+    pub(crate) fn apply(self, mut status: Status<'a>) -> (Status<'a>, ast::Node) {
+        status.pos = self.pos;
+        status.it_parsing = self.it_parsing;
+        if self.potential_error.is_some() {
+            status.potential_error = self.potential_error;
+        }
+        (status, self.node)
+    }
+
+    /// see `Error::inner_path`
+    pub(crate) fn inner_path(mut self, depth: usize) -> Self {
+        self.potential_error = self.potential_error.map(|e| e.inner_path(depth));
+        self
+    }
+
+    /// see `Error::on_path`
+    pub(crate) fn on_path(mut self, walking_rules: &[String]) -> Self {
+        self.potential_error = self.potential_error.map(|e| e.on_path(walking_rules));
+        self
+    }
+}
+
+/// Memoization table keyed by (rule name, possition)
+/// It lives just for one parse
+pub(crate) type Memo<'a> = HashMap<(&'a str, usize), result::Result<MemoEntry<'a>, Box<Error>>>;
+
+/// A rule being parsed on a possition
+#[derive(Debug)]
+pub(crate) struct RuleCall<'a> {
+    pub(crate) rule: &'a str,
+    pub(crate) pos: usize,
+    /// the rule called itself on same possition (left recursion)
+    pub(crate) left_rec: bool,
+}
+
+/// Information to support left recursion growing seeds
+/// It lives just for one parse
+#[derive(Debug, Default)]
+pub(crate) struct LeftRec<'a> {
+    /// rules in progress, possitions are never decreasing
+    pub(crate) calls: Vec<RuleCall<'a>>,
+    /// current seed of left recursive rules being grown
+    pub(crate) seeds: HashMap<(&'a str, usize), MemoEntry<'a>>,
+}
+
+impl<'a> LeftRec<'a> {
+    /// mark left recursion if the rule is in progress on the possition
+    pub(crate) fn check_recursion(&mut self, rule: &str, pos: usize) -> bool {
+        match self
+            .calls
+            .iter_mut()
+            .rev()
+            .take_while(|c| c.pos == pos)
+            .find(|c| c.rule == rule)
+        {
+            Some(call) => {
+                call.left_rec = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// results on this possition could depend on a seed not fully grown
+    pub(crate) fn growing_on(&self, pos: usize) -> bool {
+        self.seeds.keys().any(|&(_, p)| p == pos)
+            || self
+                .calls
+                .iter()
+                .rev()
+                .take_while(|c| c.pos == pos)
+                .any(|c| c.left_rec)
+    }
 }
 
 //-----------------------------------------------------------------------
@@ -110,12 +249,19 @@ pub(crate) struct Status<'a> {
     //  the error will not be processed full input
     //  It's true, but it could be more useful to know where
     //  it fail trying to repeat
-    pub(crate) potential_error: Option<Error>,
+    pub(crate) potential_error: Option<Box<Error>>,
 
     /// If true, it will fill walking rules
     /// too expensive. For use just to debug errors
     pub(crate) trace_rules: bool,
     pub(crate) walking_rules: Vec<String>,
+
+    /// If present, results of rules will be cached by possition
+    /// It's shared between all clones of the status on a parse
+    pub(crate) memo: Option<Rc<RefCell<Memo<'a>>>>,
+
+    /// Shared between all clones of the status on a parse
+    pub(crate) left_rec: Rc<RefCell<LeftRec<'a>>>,
 }
 
 impl<'a> Status<'a> {
@@ -128,6 +274,8 @@ impl<'a> Status<'a> {
             walking_rules: vec![],
             rules,
             potential_error: None,
+            memo: None,
+            left_rec: Rc::new(RefCell::new(LeftRec::default())),
         }
     }
 
@@ -144,19 +292,25 @@ impl<'a> Status<'a> {
             walking_rules: vec![],
             rules,
             potential_error: None,
+            memo: None,
+            left_rec: Rc::new(RefCell::new(LeftRec::default())),
         }
     }
+    pub(crate) fn with_memo(mut self) -> Self {
+        self.memo = Some(Rc::new(RefCell::new(Memo::new())));
+        self
+    }
     pub(crate) fn push_rule(mut self, on_node: &str) -> Self {
         self.walking_rules.push(on_node.to_string());
         self
     }
-    pub(crate) fn set_potential_error(mut self, err: Error) -> Self {
+    pub(crate) fn set_potential_error(mut self, err: Box<Error>) -> Self {
         self.potential_error = Some(err);
         self
     }
 }
 
-pub(crate) type Result<'a> = result::Result<(Status<'a>, ast::Node), Error>;
+pub(crate) type Result<'a> = result::Result<(Status<'a>, ast::Node), Box<Error>>;
 
 //-----------------------------------------------------------------------
 //-----------------------------------------------------------------------
@@ -166,6 +320,131 @@ pub(crate) type Result<'a> = result::Result<(Status<'a>, ast::Node), Error>;
 //-----------------------------------------------------------------------
 //-----------------------------------------------------------------------
 
+impl Error {
+    /// possition where the error was produced
+    pub fn pos(&self) -> &Possition {
+        &self.pos
+    }
+    /// what was expected and the context
+    pub fn alternatives(&self) -> &ErrorAlternatives {
+        &self.alternatives
+    }
+    /// expected options (deduplicated)
+    pub fn expected(&self) -> Vec<&str> {
+        self.alternatives.expected()
+    }
+    /// description of the rule where the error was produced (`.desc`)
+    pub fn context(&self) -> Option<&str> {
+        self.alternatives.context()
+    }
+    /// text on the error line before error possition
+    pub fn line_before(&self) -> &str {
+        &self.line_before
+    }
+    /// text on the error line after error possition
+    pub fn line_after(&self) -> &str {
+        &self.line_after
+    }
+    /// rules path followed till got the error
+    /// Only available parsing with debug
+    pub fn parsing_rules(&self) -> &[String] {
+        &self.parsing_rules
+    }
+    /// error priority
+    pub fn priority(&self) -> ErrPriority {
+        self.priority
+    }
+}
+
+impl ErrorAlternatives {
+    /// expected options, removing duplicates and keeping the order
+    pub fn expected(&self) -> Vec<&str> {
+        self.expected.iter().fold(vec![], |mut acc, e| {
+            if !acc.contains(&e.as_str()) {
+                acc.push(e.as_str());
+            }
+            acc
+        })
+    }
+    /// description of the rule where the error was produced (`.desc`)
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+}
+
+//  compiler style diagnostic
+//
+//  error: expected "b", "c"   (or "unexpected input" when nothing is expected)
+//   --> 2:5
+//    |
+//  2 | let a = b
+//    |     ^
+//    = rule: Unary expression
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let expected = self
+            .expected()
+            .iter()
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = (self.pos.row + 1).to_string();
+        let margin = " ".repeat(line.len());
+        let caret = " ".repeat(self.line_before.chars().count());
+
+        if expected.is_empty() {
+            writeln!(f, "error: unexpected input")?;
+        } else {
+            writeln!(f, "error: expected {}", expected)?;
+        }
+        writeln!(f, "{}--> {}:{}", margin, line, self.pos.col + 1)?;
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}{}", line, self.line_before, self.line_after)?;
+        write!(f, "{} | {}^", margin, caret)?;
+        if let Some(context) = self.context() {
+            write!(f, "\n{} = rule: {}", margin, context)?;
+        }
+        if !self.parsing_rules.is_empty() {
+            write!(
+                f,
+                "\n{} = parsing: {}",
+                margin,
+                self.parsing_rules.join(" > ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Possition {
+    /// byte offset on parsed text
+    pub fn n(&self) -> usize {
+        self.n
+    }
+    /// chars from the beginning of parsed text
+    pub fn n_char(&self) -> usize {
+        self.n_char
+    }
+    /// row, starting on 0
+    pub fn row(&self) -> usize {
+        self.row
+    }
+    /// column in chars, starting on 0
+    pub fn col(&self) -> usize {
+        self.col
+    }
+    /// column in UTF-16 code units, starting on 0
+    pub fn col_utf16(&self) -> usize {
+        self.col_utf16
+    }
+    /// byte offset where current line starts
+    pub fn start_line(&self) -> usize {
+        self.start_line
+    }
+}
+
 //-----------------------------------------------------------------------
 //  T E S T
 //-----------------------------------------------------------------------
@@ -176,43 +455,64 @@ mod test;
 //  I N T E R N A L
 //-----------------------------------------------------------------------
 impl Error {
-    pub(crate) fn with_context(mut self, context: &str) -> Self {
+    pub(crate) fn with_context(mut self: Box<Self>, context: &str) -> Box<Self> {
         if self.alternatives.context.is_none() && !context.is_empty() {
             self.alternatives.context = Some(context.to_owned());
         }
         self
     }
+    /// rules path inside the rule started after `depth` walking rules
+    /// A memoized error can be reused from another path
+    pub(crate) fn inner_path(mut self: Box<Self>, depth: usize) -> Box<Self> {
+        let depth = depth.min(self.parsing_rules.len());
+        self.parsing_rules.drain(..depth);
+        self
+    }
+    /// memoized error reused on the path of `walking_rules`
+    pub(crate) fn on_path(mut self: Box<Self>, walking_rules: &[String]) -> Box<Self> {
+        self.parsing_rules
+            .splice(..0, walking_rules.iter().cloned());
+        self
+    }
     pub(crate) fn from_status_simple(status: &Status, descr: &str, prior: ErrPriority) -> Self {
+        Self::from_status(status, &ErrorAlternatives::from_string(descr), prior)
+    }
+    pub(crate) fn from_status(
+        status: &Status,
+        alternatives: &ErrorAlternatives,
+        prior: ErrPriority,
+    ) -> Self {
+        Self::from_status_at(status, &status.pos, alternatives, prior)
+    }
+    /// error on a previous possition of status
+    pub(crate) fn from_status_at(
+        status: &Status,
+        pos: &Possition,
+        alternatives: &ErrorAlternatives,
+        prior: ErrPriority,
+    ) -> Self {
         Error {
-            pos: status.pos.clone(),
-            alternatives: ErrorAlternatives::from_string(descr),
-            line_before: status.text2parse[status.pos.start_line..status.pos.n].to_string(),
-            line_after: status
-                .it_parsing
-                .clone()
-                .take_while(|&ch| ch != '\n' && ch != '\r')
-                .collect(),
-            // errors: vec![],
             parsing_rules: status.walking_rules.clone(),
-            priority: prior,
+            ..Self::from_text_at(status.text2parse, pos, alternatives, prior)
         }
     }
-    pub(crate) fn from_status(
-        status: &Status,
+    /// error on a possition of the parsed text
+    pub(crate) fn from_text_at(
+        text: &str,
+        pos: &Possition,
         alternatives: &ErrorAlternatives,
         prior: ErrPriority,
     ) -> Self {
         Error {
-            pos: status.pos.clone(),
+            pos: pos.clone(),
             alternatives: alternatives.clone(),
-            line_before: status.text2parse[status.pos.start_line..status.pos.n].to_string(),
-            line_after: status
-                .it_parsing
-                .clone()
+            line_before: text[pos.start_line..pos.n].to_string(),
+            line_after: text[pos.n..]
+                .chars()
                 .take_while(|&ch| ch != '\n' && ch != '\r')
                 .collect(),
             // errors: vec![],
-            parsing_rules: status.walking_rules.clone(),
+            parsing_rules: vec![],
             priority: prior,
         }
     }
@@ -242,6 +542,15 @@ impl Error {
         )
     }
 
+    pub(crate) fn from_status_normal_at(status: &Status, pos: &Possition, descr: &str) -> Self {
+        Self::from_status_at(
+            status,
+            pos,
+            &ErrorAlternatives::from_string(descr),
+            ErrPriority::Normal,
+        )
+    }
+
     // pub(crate) fn from_st_errs(status: &Status, descr: &str, errors: Vec<Error>) -> Self {
     //     let max_pr = |verrors: &Vec<Error>| {
     //         use std::cmp::max;
@@ -0,0 +1,328 @@
+#![warn(missing_docs)]
+//! Support for the specialized parsers generated with
+//! `SetOfRules::specialized_parser`
+//!
+//! Generated code calls these functions, they replicate what the
+//! interpreter does on `parser::expression`, in order to produce the
+//! same `ast` and errors
+
+use crate::ast;
+use crate::parser::expression::ReplTemplate;
+use crate::parser::{ErrPriority, Error, ErrorAlternatives, Possition};
+use std::result;
+
+#[cfg(test)]
+mod test;
+//  generated from rules_for_peg.peg (running the binary)
+#[cfg(test)]
+#[rustfmt::skip]
+mod peg_parser;
+
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+//
+//  T Y P E S
+//
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+
+/// Parsing state of a specialized parser
+#[derive(Debug, Clone)]
+pub struct State<'a> {
+    text: &'a str,
+    pos: Possition,
+    potential_error: Option<Box<Error>>,
+}
+
+/// Result of parsing an expression
+pub type Result<'a> = result::Result<(State<'a>, Vec<ast::Node>), Box<Error>>;
+
+/// First difference between the interpreter and a specialized parser
+/// parsing `text`  (see `SetOfRules::compare_specialized`)
+#[derive(Debug, PartialEq, Clone)]
+pub struct Mismatch<'t> {
+    /// parsed text
+    pub text: &'t str,
+    /// nodes from the root to the first different one
+    /// (on errors, the parsing rules of the error if they were traced)
+    pub path: Vec<String>,
+    /// span of the first different node on the interpreter `ast`
+    /// (on errors, the error possition)
+    pub span: ast::Span,
+}
+
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+//
+//  A P I
+//
+//-----------------------------------------------------------------------
+//-----------------------------------------------------------------------
+
+impl<'a> State<'a> {
+    /// start parsing `text`
+    pub fn new(text: &'a str) -> Self {
+        State {
+            text,
+            pos: Possition::init(),
+            potential_error: None,
+        }
+    }
+
+    /// current possition
+    pub fn pos(&self) -> &Possition {
+        &self.pos
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let ch = self.text[self.pos.n..].chars().next()?;
+        self.pos.advance(ch);
+        Some(ch)
+    }
+
+    fn error_at(&self, pos: &Possition, descr: &str, prior: ErrPriority) -> Box<Error> {
+        Box::new(Error::from_text_at(
+            self.text,
+            pos,
+            &ErrorAlternatives::from_string(descr),
+            prior,
+        ))
+    }
+}
+
+/// literal
+pub fn lit<'a>(mut st: State<'a>, literal: &str) -> Result<'a> {
+    let start = st.pos.clone();
+    for ch in literal.chars() {
+        let pos = st.pos.clone();
+        if st.next_char() != Some(ch) {
+            return Err(st.error_at(&pos, literal, ErrPriority::Normal));
+        }
+    }
+    val(st, start, literal)
+}
+
+/// a char matching `matches`, described on errors with `descr`
+pub fn char_match<'a>(mut st: State<'a>, matches: fn(char) -> bool, descr: &str) -> Result<'a> {
+    let start = st.pos.clone();
+    match st.next_char() {
+        Some(ch) if matches(ch) => val(st, start, &ch.to_string()),
+        _ => Err(st.error_at(&start, descr, ErrPriority::Normal)),
+    }
+}
+
+/// any char
+pub fn dot(mut st: State) -> Result {
+    let start = st.pos.clone();
+    match st.next_char() {
+        Some(ch) => val(st, start, &ch.to_string()),
+        None => Err(st.error_at(&st.pos, "anything", ErrPriority::Normal)),
+    }
+}
+
+/// end of input
+pub fn eof(st: State) -> Result {
+    let start = st.pos.clone();
+    let mut next = st.clone();
+    match next.next_char() {
+        Some(_) => Err(st.error_at(&start, "expected EOF", ErrPriority::Normal)),
+        None => val(st, start, "EOF"),
+    }
+}
+
+/// critical error  ie: `expected("a number")`
+pub fn expected<'a>(st: State<'a>, descr: &str) -> Result<'a> {
+    Err(st.error_at(&st.pos, descr, ErrPriority::Critical))
+}
+
+/// succeed, without consuming, if `result` (parsed from `st`) failed
+pub fn not<'a>(st: State<'a>, result: Result<'a>) -> Result<'a> {
+    match result {
+        Ok(_) => Err(st.error_at(&st.pos, "not", ErrPriority::Normal)),
+        Err(_) => Ok((st, vec![])),
+    }
+}
+
+/// succeed, without consuming, if `result` (parsed from `st`) succeeded
+pub fn peek<'a>(st: State<'a>, result: Result<'a>) -> Result<'a> {
+    match result {
+        Ok(_) => Ok((st, vec![])),
+        Err(_) => Err(st.error_at(&st.pos, "not", ErrPriority::Normal)),
+    }
+}
+
+/// accumulate the error of an `or` alternative
+///
+/// Critical errors stop trying alternatives
+pub fn or_error(
+    acc: Option<Box<Error>>,
+    err: Box<Error>,
+) -> result::Result<Option<Box<Error>>, Box<Error>> {
+    match acc {
+        _ if err.priority == ErrPriority::Critical => Err(err),
+        Some(acc) if acc.priority > err.priority => Ok(Some(acc)),
+        Some(acc) => Ok(Some(Box::new(acc.merge(&err)))),
+        None => Ok(Some(err)),
+    }
+}
+
+/// error of an `or` when no alternative matched
+pub fn or_end(st: &State, acc: Option<Box<Error>>) -> Box<Error> {
+    acc.unwrap_or_else(|| {
+        st.error_at(
+            &st.pos,
+            "LOGIC ERROR!!! checked all options in or with ¿NO? errors",
+            ErrPriority::Normal,
+        )
+    })
+}
+
+/// a repetition stopped on `err`, after the minimum
+pub fn repeat_end<'a>(mut st: State<'a>, nodes: Vec<ast::Node>, err: Box<Error>) -> Result<'a> {
+    if err.priority == ErrPriority::Critical {
+        Err(err)
+    } else {
+        st.potential_error = Some(err);
+        Ok((st, nodes))
+    }
+}
+
+/// the repetition made no progress
+pub fn same_pos(st1: &State, st2: &State) -> bool {
+    st1.pos.n == st2.pos.n
+}
+
+/// rule node, from `start`
+pub fn rule<'a>(
+    name: &str,
+    descr: Option<&str>,
+    start: Possition,
+    result: Result<'a>,
+) -> Result<'a> {
+    let (st, nodes) = result.map_err(|err| err.with_context(descr.unwrap_or("")))?;
+    let span = ast::Span::new(start, st.pos.clone());
+    Ok((st, vec![ast::Node::Rule((name.to_owned(), nodes, span))]))
+}
+
+/// named node, from `start`
+pub fn named<'a>(name: &str, start: Possition, result: Result<'a>) -> Result<'a> {
+    let (st, nodes) = result?;
+    let span = ast::Span::new(start, st.pos.clone());
+    Ok((st, vec![ast::Node::Named((name.to_owned(), nodes, span))]))
+}
+
+/// node with a replace template, from `start`
+pub fn transf2<'a>(
+    start: Possition,
+    result: Result<'a>,
+    template: impl FnOnce() -> ReplTemplate,
+) -> Result<'a> {
+    let (st, nodes) = result?;
+    let span = ast::Span::new(start, st.pos.clone());
+    Ok((
+        st,
+        vec![ast::Node::Transf2(ast::Transf2 {
+            template: template(),
+            nodes,
+            span,
+        })],
+    ))
+}
+
+/// starting on a rule not defined on the grammar
+pub fn missing_rule<'a>(st: State<'a>, rule: &str) -> Result<'a> {
+    Err(st.error_at(
+        &st.pos,
+        &format!("Missing rule: {}", rule),
+        ErrPriority::Critical,
+    ))
+}
+
+/// the `ast` if the full text was consumed
+pub fn finish(result: Result) -> result::Result<ast::Node, Box<Error>> {
+    let (st, mut nodes) = result?;
+    match (st.pos.n == st.text.len(), st.potential_error.clone()) {
+        (true, _) => Ok(nodes.remove(0)),
+        (false, Some(e)) => Err(e),
+        (false, None) => Err(st.error_at(&st.pos, "not consumed full input", ErrPriority::Normal)),
+    }
+}
+
+/// first difference between the result of the interpreter and the
+/// result of the specialized parser, `None` if they are equal
+pub(crate) fn mismatch<'t>(
+    text: &'t str,
+    interpreted: &result::Result<ast::Node, Box<Error>>,
+    specialized: &result::Result<ast::Node, Box<Error>>,
+) -> Option<Mismatch<'t>> {
+    let error_at = |e: &Error| {
+        (
+            e.parsing_rules.clone(),
+            ast::Span::new(e.pos.clone(), e.pos.clone()),
+        )
+    };
+    let (path, span) = match (interpreted, specialized) {
+        (Ok(n1), Ok(n2)) => node_diff(n1, n2)?,
+        (Err(e1), Err(e2)) if e1 == e2 => return None,
+        (Err(e), _) | (Ok(_), Err(e)) => error_at(e),
+    };
+    Some(Mismatch { text, path, span })
+}
+
+//-----------------------------------------------------------------------
+//  SUPPORT
+
+fn node_diff(n1: &ast::Node, n2: &ast::Node) -> Option<(Vec<String>, ast::Span)> {
+    use ast::Node;
+
+    if n1 == n2 {
+        return None;
+    }
+    let children_diff = |nodes1: &[Node], nodes2: &[Node]| {
+        if nodes1.len() == nodes2.len() {
+            nodes1
+                .iter()
+                .zip(nodes2)
+                .find_map(|(n1, n2)| node_diff(n1, n2))
+        } else {
+            None
+        }
+    };
+    let diff = match (n1, n2) {
+        (Node::Rule((name1, nodes1, span1)), Node::Rule((name2, nodes2, span2)))
+        | (Node::Named((name1, nodes1, span1)), Node::Named((name2, nodes2, span2)))
+            if name1 == name2 && span1 == span2 =>
+        {
+            children_diff(nodes1, nodes2)
+        }
+        (Node::Transf2(t1), Node::Transf2(t2))
+            if t1.template == t2.template && t1.span == t2.span =>
+        {
+            children_diff(&t1.nodes, &t2.nodes)
+        }
+        _ => None,
+    };
+
+    let mut path = vec![node_name(n1)];
+    match diff {
+        Some((inner_path, span)) => {
+            path.extend(inner_path);
+            Some((path, span))
+        }
+        None => Some((path, n1.span().clone())),
+    }
+}
+
+fn node_name(node: &ast::Node) -> String {
+    match node {
+        ast::Node::Val((v, _)) => format!("{:?}", v),
+        ast::Node::Rule((name, _, _)) | ast::Node::Named((name, _, _)) => name.clone(),
+        ast::Node::Transf2(_) => "->".to_string(),
+        ast::Node::Eof(_) => "eof".to_string(),
+    }
+}
+
+fn val<'a>(st: State<'a>, start: Possition, v: &str) -> Result<'a> {
+    let span = ast::Span::new(start, st.pos.clone());
+    Ok((st, vec![ast::Node::Val((v.to_owned(), span))]))
+}
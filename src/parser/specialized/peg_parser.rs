@@ -0,0 +1,2192 @@
+#[allow(unused_imports)]
+use yepp::{
+    and, dot, ematch, eof, expected, lit, named, not, or, peek, ref_rule, rep, rules, t2_byname,
+    t2_byname_count, t2_byname_index, t2_byname_join, t2_byname_opt, t2_bypos, t2_funct,
+    t2_if_eq, t2_if_named, t2_text, t2rules, transf2,
+};
+#[allow(unused_imports)]
+use yepp::grammar::NameIndex;
+
+use yepp::specialized::{self, State};
+
+/// parse `text` starting on rule `main`, it has to be consumed completely
+pub fn parse(text: &str) -> Result<yepp::Node, Box<yepp::ParserError>> {
+    parse_from("main", text)
+}
+
+/// parse `text` starting on `rule`, it has to be consumed completely
+pub fn parse_from(rule: &str, text: &str) -> Result<yepp::Node, Box<yepp::ParserError>> {
+    let st = State::new(text);
+    specialized::finish(match rule {
+        "main" => r_main(st),
+        "grammar" => r_grammar(st),
+        "symbol" => r_symbol(st),
+        "rule" => r_rule(st),
+        "rule_name" => r_rule_name(st),
+        "rule_descr" => r_rule_descr(st),
+        "expr" => r_expr(st),
+        "or" => r_or(st),
+        "and" => r_and(st),
+        "andline_transf2" => r_andline_transf2(st),
+        "just_andline" => r_just_andline(st),
+        "no_rule_name" => r_no_rule_name(st),
+        "expected" => r_expected(st),
+        "andline" => r_andline(st),
+        "andchunk" => r_andchunk(st),
+        "_1" => r__1(st),
+        "rep_or_unary" => r_rep_or_unary(st),
+        "rep_symbol" => r_rep_symbol(st),
+        "atom_or_par" => r_atom_or_par(st),
+        "parenth" => r_parenth(st),
+        "atom" => r_atom(st),
+        "literal" => r_literal(st),
+        "lit_noesc" => r_lit_noesc(st),
+        "_'" => r___u27_(st),
+        "lit_esc" => r_lit_esc(st),
+        "_\"" => r___u22_(st),
+        "esc_char" => r_esc_char(st),
+        "hex_char" => r_hex_char(st),
+        "eol" => r_eol(st),
+        "_eol" => r__eol(st),
+        "match" => r_match(st),
+        "mchars" => r_mchars(st),
+        "mbetween" => r_mbetween(st),
+        "dot" => r_dot(st),
+        "_" => r__(st),
+        "comment" => r_comment(st),
+        "line_comment" => r_line_comment(st),
+        "mline_comment" => r_mline_comment(st),
+        "name" => r_name(st),
+        "transf2" => r_transf2(st),
+        "transf_rule" => r_transf_rule(st),
+        "tmpl_text" => r_tmpl_text(st),
+        "tmpl_rule" => r_tmpl_rule(st),
+        "tmpl_if" => r_tmpl_if(st),
+        "tmpl_cond" => r_tmpl_cond(st),
+        "tmpl_else" => r_tmpl_else(st),
+        "tmpl_block" => r_tmpl_block(st),
+        "tmpl_block_text" => r_tmpl_block_text(st),
+        "tmpl_lit" => r_tmpl_lit(st),
+        "tmpl_sep" => r_tmpl_sep(st),
+        "tmpl_idx" => r_tmpl_idx(st),
+        _ => specialized::missing_rule(st, rule),
+    })
+}
+
+/// rule `main`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_main(st: State) -> specialized::Result {
+    specialized::rule("main", None, st.pos().clone(), specialized::transf2(st.pos().clone(), r_grammar(st), || t2rules!(t2_byname!("grammar"), t2_text!("EOP"), )))
+}
+
+/// rule `grammar`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_grammar(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_rule(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    specialized::rule("grammar", None, st.pos().clone(), e1(st))
+}
+
+/// rule `symbol`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_symbol(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::char_match(st.clone(), |ch| matches!(ch, '_' | '\'' | '"' | 'a'..='z' | 'A'..='Z' | '0'..='9'), "match _'\" [('a', 'z'), ('A', 'Z'), ('0', '9')]") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::char_match(st, |ch| matches!(ch, '_' | 'a'..='z' | 'A'..='Z' | '0'..='9'), "match _ [('a', 'z'), ('A', 'Z'), ('0', '9')]")?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("symbol", None, st.pos().clone(), e2(st))
+}
+
+/// rule `rule`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_rule(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_rule_descr(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_rule_name(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "=")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_expr(st)?;
+        nodes.extend(n);
+        let (st, n) = r__eol(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("desc", st.pos().clone(), e1(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("rule", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e2(st), || t2rules!(t2_text!("RULE"), t2_funct!("endl"), t2_byname!("rule_name"), t2_funct!("endl"), t2_text!("DESCR"), t2_funct!("endl"), t2_byname!("desc"), t2_funct!("endl"), t2_byname!("expr"), )))
+}
+
+/// rule `rule_name`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_rule_name(st: State) -> specialized::Result {
+    specialized::rule("rule_name", None, st.pos().clone(), r_symbol(st))
+}
+
+/// rule `rule_descr`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_rule_descr(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), specialized::lit(st, "desc."))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, ".desc")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("desc", st.pos().clone(), e2(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "desc.")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("rule_descr", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e3(st), || t2rules!(t2_byname!("desc"), )))
+}
+
+/// rule `expr`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_expr(st: State) -> specialized::Result {
+    specialized::rule("expr", None, st.pos().clone(), specialized::transf2(st.pos().clone(), r_or(st), || t2rules!(t2_text!("OR"), t2_funct!("endl"), t2_byname!("or"), t2_text!("CLOSE_MEXPR"), t2_funct!("endl"), )))
+}
+
+/// rule `or`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_or(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_and(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "/")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_or(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e2(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_text!("AND"), t2_funct!("endl"), t2_byname!("and"), t2_text!("CLOSE_MEXPR"), t2_funct!("endl"), ))?;
+        nodes.extend(n);
+        let (st, n) = specialized::transf2(st.pos().clone(), e3(st), || t2rules!(t2_byname!("or"), ))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("or", None, st.pos().clone(), e4(st))
+}
+
+/// rule `and`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_and(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_expected(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_andline_transf2(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_just_andline(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("and", None, st.pos().clone(), e1(st))
+}
+
+/// rule `andline_transf2`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_andline_transf2(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_no_rule_name(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::not(st.clone(), r_rule_descr(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), r__(st), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = r_and(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e2(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_andline(st)?;
+        nodes.extend(n);
+        let (st, n) = r_transf2(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("and", st.pos().clone(), e3(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("andline_transf2", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e4(st), || t2rules!(t2_text!("TRANSF2"), t2_funct!("endl"), t2_byname!("transf2"), t2_text!("EOTRANSF2"), t2_funct!("endl"), t2_text!("AND"), t2_funct!("endl"), t2_byname!("andline"), t2_text!("CLOSE_MEXPR"), t2_funct!("endl"), t2_byname!("and"), )))
+}
+
+/// rule `just_andline`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_just_andline(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match r_eol(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__eol(st)?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_no_rule_name(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::not(st.clone(), r_rule_descr(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), e2(st), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = e3(st)?;
+        nodes.extend(n);
+        let (st, n) = r_and(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e4(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e6(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_andline(st)?;
+        nodes.extend(n);
+        let (st, n) = e5(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("just_andline", None, st.pos().clone(), e6(st))
+}
+
+/// rule `no_rule_name`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_no_rule_name(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_rule_name(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "=")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("no_rule_name", None, st.pos().clone(), specialized::not(st.clone(), e1(st)))
+}
+
+/// rule `expected`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_expected(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "expected")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "(")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_literal(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, ")")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("expected", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_text!("EXPECTED"), t2_funct!("endl"), t2_byname!("literal"), t2_funct!("endl"), )))
+}
+
+/// rule `andline`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_andline(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_expected(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_andchunk(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e3(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_andchunk(st)?;
+        nodes.extend(n);
+        let (st, n) = e4(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("andline", None, st.pos().clone(), e5(st))
+}
+
+/// rule `andchunk`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_andchunk(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_name(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("e", st.pos().clone(), r_rep_or_unary(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), e1(st.clone()), || t2rules!(t2_text!("NAMED"), t2_funct!("endl"), t2_byname!("name"), t2_funct!("endl"), t2_byname!("e"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_rep_or_unary(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("andchunk", None, st.pos().clone(), e2(st))
+}
+
+/// rule `_1`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r__1(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), " ") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), r_eol(st.clone()), || t2rules!(t2_funct!("none"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("_1", None, st.pos().clone(), e1(st))
+}
+
+/// rule `rep_or_unary`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_rep_or_unary(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_rep_symbol(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_atom_or_par(st)?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "!")?;
+        nodes.extend(n);
+        let (st, n) = r_atom_or_par(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "&")?;
+        nodes.extend(n);
+        let (st, n) = r_atom_or_par(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), e2(st.clone()), || t2rules!(t2_byname_opt!("rep_symbol"), t2_byname!("atom_or_par"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e3(st.clone()), || t2rules!(t2_text!("NEGATE"), t2_funct!("endl"), t2_byname!("atom_or_par"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e4(st.clone()), || t2rules!(t2_text!("PEEK"), t2_funct!("endl"), t2_byname!("atom_or_par"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("rep_or_unary", None, st.pos().clone(), e5(st))
+}
+
+/// rule `rep_symbol`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_rep_symbol(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), specialized::lit(st.clone(), "*"), || t2rules!(t2_text!("REPEAT"), t2_funct!("endl"), t2_text!("0"), t2_funct!("endl"), t2_text!("inf"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), specialized::lit(st.clone(), "+"), || t2rules!(t2_text!("REPEAT"), t2_funct!("endl"), t2_text!("1"), t2_funct!("endl"), t2_text!("inf"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), specialized::lit(st.clone(), "?"), || t2rules!(t2_text!("REPEAT"), t2_funct!("endl"), t2_text!("0"), t2_funct!("endl"), t2_text!("1"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("rep_symbol", None, st.pos().clone(), e1(st))
+}
+
+/// rule `atom_or_par`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_atom_or_par(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_atom(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_parenth(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("atom_or_par", None, st.pos().clone(), e1(st))
+}
+
+/// rule `parenth`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_parenth(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "(")?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = r_expr(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), specialized::lit(st.clone(), ")"), || t2rules!(t2_funct!("none"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::expected(st.clone(), "unbalanced parethesis: missing ')'") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_byname!("expr"), ))?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("parenth", None, st.pos().clone(), e3(st))
+}
+
+/// rule `atom`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_atom(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), specialized::named("a", st.pos().clone(), r_literal(st.clone())), || t2rules!(t2_text!("ATOM"), t2_funct!("endl"), t2_text!("LIT"), t2_funct!("endl"), t2_byname!("a"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), specialized::named("a", st.pos().clone(), r_match(st.clone())), || t2rules!(t2_text!("MATCH"), t2_funct!("endl"), t2_byname!("a"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), specialized::named("a", st.pos().clone(), r_rule_name(st.clone())), || t2rules!(t2_text!("ATOM"), t2_funct!("endl"), t2_text!("RULREF"), t2_funct!("endl"), t2_byname!("a"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), r_dot(st.clone()), || t2rules!(t2_text!("ATOM"), t2_funct!("endl"), t2_text!("DOT"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("atom", None, st.pos().clone(), e1(st))
+}
+
+/// rule `literal`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_literal(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_lit_noesc(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_lit_esc(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("literal", None, st.pos().clone(), e1(st))
+}
+
+/// rule `lit_noesc`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_lit_noesc(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), r___u27_(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r___u27_(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("l", st.pos().clone(), e2(st))?;
+        nodes.extend(n);
+        let (st, n) = r___u27_(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("lit_noesc", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e3(st), || t2rules!(t2_byname!("l"), )))
+}
+
+/// rule `_'`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r___u27_(st: State) -> specialized::Result {
+    specialized::rule("_'", None, st.pos().clone(), specialized::lit(st, "'"))
+}
+
+/// rule `lit_esc`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_lit_esc(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), r___u22_(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_esc_char(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_hex_char(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match e1(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e2(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r___u22_(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("l", st.pos().clone(), e3(st))?;
+        nodes.extend(n);
+        let (st, n) = r___u22_(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("lit_esc", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e4(st), || t2rules!(t2_byname!("l"), )))
+}
+
+/// rule `_"`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r___u22_(st: State) -> specialized::Result {
+    specialized::rule("_\"", None, st.pos().clone(), specialized::lit(st, "\""))
+}
+
+/// rule `esc_char`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_esc_char(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "\\r") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\n") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\t") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\\\") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\\\\"") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("esc_char", None, st.pos().clone(), e1(st))
+}
+
+/// rule `hex_char`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_hex_char(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "\\0x")?;
+        nodes.extend(n);
+        let (st, n) = specialized::char_match(st, |ch| matches!(ch, '0'..='9' | 'A'..='F'), "match  [('0', '9'), ('A', 'F')]")?;
+        nodes.extend(n);
+        let (st, n) = specialized::char_match(st, |ch| matches!(ch, '0'..='9' | 'A'..='F'), "match  [('0', '9'), ('A', 'F')]")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("hex_char", None, st.pos().clone(), e1(st))
+}
+
+/// rule `eol`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_eol(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "\\r\\n") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\n") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "\\r") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("eol", None, st.pos().clone(), e1(st))
+}
+
+/// rule `_eol`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r__eol(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), " ") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_comment(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        let (st, n) = r_eol(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("_eol", None, st.pos().clone(), e3(st))
+}
+
+/// rule `match`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_match(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match r_mbetween(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_mchars(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("b", st.pos().clone(), e1(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_mbetween(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), e2(st.clone()), || t2rules!(t2_text!("CHARS"), t2_funct!("endl"), t2_byname!("mchars"), t2_funct!("endl"), t2_text!("BETW"), t2_funct!("endl"), t2_byname!("b"), t2_text!("EOBETW"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), specialized::named("b", st.pos().clone(), e3(st.clone())), || t2rules!(t2_text!("BETW"), t2_funct!("endl"), t2_byname!("b"), t2_text!("EOBETW"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::lit(st, "["), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = e4(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::lit(st, "]"), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("match", None, st.pos().clone(), e5(st))
+}
+
+/// rule `mchars`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_mchars(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "-")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), specialized::lit(st, "]"))?;
+        nodes.extend(n);
+        let (st, n) = specialized::not(st.clone(), e1(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e2(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    specialized::rule("mchars", None, st.pos().clone(), e3(st))
+}
+
+/// rule `mbetween`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_mbetween(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::named("f", st.pos().clone(), specialized::dot(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "-")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("s", st.pos().clone(), specialized::dot(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("mbetween", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_byname!("f"), t2_funct!("endl"), t2_byname!("s"), t2_funct!("endl"), )))
+}
+
+/// rule `dot`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_dot(st: State) -> specialized::Result {
+    specialized::rule("dot", None, st.pos().clone(), specialized::lit(st, "."))
+}
+
+/// rule `_`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r__(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), " ") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_eol(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_comment(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    specialized::rule("_", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e2(st), || t2rules!(t2_funct!("none"), )))
+}
+
+/// rule `comment`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_comment(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_line_comment(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_mline_comment(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("comment", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_funct!("none"), )))
+}
+
+/// rule `line_comment`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_line_comment(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), r_eol(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "//")?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("line_comment", None, st.pos().clone(), e3(st))
+}
+
+/// rule `mline_comment`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_mline_comment(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), specialized::lit(st, "*/"))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "/*")?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "*/")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("mline_comment", None, st.pos().clone(), e3(st))
+}
+
+/// rule `name`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_name(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, ":")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("name", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e1(st), || t2rules!(t2_byname!("symbol"), )))
+}
+
+/// rule `transf2`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_transf2(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r__1(st)?;
+        nodes.extend(n);
+        let (st, n) = r__(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "->")?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), e2(st), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = specialized::transf2(st.pos().clone(), r_transf_rule(st), || t2rules!(t2_byname!("transf_rule"), ))?;
+        nodes.extend(n);
+        let (st, n) = specialized::peek(st.clone(), r_eol(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("transf2", None, st.pos().clone(), e3(st))
+}
+
+/// rule `transf_rule`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_transf_rule(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::peek(st.clone(), r_eol(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), e2(st.clone()), || t2rules!(t2_funct!("none"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_tmpl_text(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_tmpl_rule(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e3(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    specialized::rule("transf_rule", None, st.pos().clone(), e4(st))
+}
+
+/// rule `tmpl_text`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_text(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "$(") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_eol(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), e1(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e2(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    specialized::rule("tmpl_text", None, st.pos().clone(), specialized::transf2(st.pos().clone(), specialized::named("t", st.pos().clone(), e3(st)), || t2rules!(t2_text!("TEXT"), t2_funct!("endl"), t2_byname!("t"), t2_funct!("endl"), )))
+}
+
+/// rule `tmpl_rule`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_rule(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "?")?;
+        nodes.extend(n);
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "#")?;
+        nodes.extend(n);
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_tmpl_sep(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "*")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("sep", st.pos().clone(), e3(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "[")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("idx", st.pos().clone(), r_tmpl_idx(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "]")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e6(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::char_match(st.clone(), |ch| matches!(ch, '0'..='9'), "match  [('0', '9')]") {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e7(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, ".")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("pos", st.pos().clone(), e6(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e8(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), ")") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_eol(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e9(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), e8(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e10(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e9(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e11(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::lit(st, ":"), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::named("fn", st.pos().clone(), e10(st)), || t2rules!(t2_text!("FUNCT"), t2_funct!("endl"), t2_byname!("fn"), t2_funct!("endl"), ))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e12(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_tmpl_if(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e1(st.clone()), || t2rules!(t2_text!("NAMED_OPT"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e2(st.clone()), || t2rules!(t2_text!("NAMED_COUNT"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e4(st.clone()), || t2rules!(t2_text!("NAMED_JOIN"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), t2_byname_opt!("sep"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e5(st.clone()), || t2rules!(t2_text!("NAMED_INDEX"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), t2_byname!("idx"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), r_symbol(st.clone()), || t2rules!(t2_text!("NAMED"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e7(st.clone()), || t2rules!(t2_text!("POS"), t2_funct!("endl"), t2_byname!("pos"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match e11(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e13(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::lit(st, "$("), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        let (st, n) = e12(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::transf2(st.pos().clone(), specialized::lit(st, ")"), || t2rules!(t2_funct!("none"), ))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("tmpl_rule", None, st.pos().clone(), e13(st))
+}
+
+/// rule `tmpl_if`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_if(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match r_tmpl_else(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "?")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("cond", st.pos().clone(), r_tmpl_cond(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, ":")?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("then", st.pos().clone(), r_tmpl_block(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("else", st.pos().clone(), e2(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("tmpl_if", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e3(st), || t2rules!(t2_text!("IF"), t2_funct!("endl"), t2_byname!("cond"), t2_byname!("then"), t2_text!("ELSE"), t2_funct!("endl"), t2_byname_opt!("else"), t2_text!("END_IF"), t2_funct!("endl"), )))
+}
+
+/// rule `tmpl_cond`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_cond(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "==")?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "\"")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("l", st.pos().clone(), r_tmpl_lit(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "\"")?;
+        nodes.extend(n);
+        let (st, n) = e3(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e6(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = r_symbol(st)?;
+        nodes.extend(n);
+        let (st, n) = e5(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e7(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::transf2(st.pos().clone(), e4(st.clone()), || t2rules!(t2_text!("EQ"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), t2_byname!("l"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::transf2(st.pos().clone(), e6(st.clone()), || t2rules!(t2_text!("NAMED"), t2_funct!("endl"), t2_byname!("symbol"), t2_funct!("endl"), )) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("tmpl_cond", None, st.pos().clone(), e7(st))
+}
+
+/// rule `tmpl_else`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_else(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if count + 1 == 1 || specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "|")?;
+        nodes.extend(n);
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("b", st.pos().clone(), r_tmpl_block(st))?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("tmpl_else", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e2(st), || t2rules!(t2_byname!("b"), )))
+}
+
+/// rule `tmpl_block`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_block(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match r_tmpl_block_text(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_tmpl_rule(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e1(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    specialized::rule("tmpl_block", None, st.pos().clone(), e2(st))
+}
+
+/// rule `tmpl_block_text`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_block_text(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "$(") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_eol(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "|") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), ")") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), e1(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match e2(st.clone()) {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    specialized::rule("tmpl_block_text", None, st.pos().clone(), specialized::transf2(st.pos().clone(), specialized::named("t", st.pos().clone(), e3(st)), || t2rules!(t2_text!("TEXT"), t2_funct!("endl"), t2_byname!("t"), t2_funct!("endl"), )))
+}
+
+/// rule `tmpl_lit`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_lit(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::lit(st, "\\\\")?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "\"") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match r_eol(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = specialized::not(st.clone(), e2(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::dot(st)?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    fn e4(st: State) -> specialized::Result {
+        let err = None;
+        let err = match e1(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match e3(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    fn e5(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match e4(st.clone()) {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    specialized::rule("tmpl_lit", None, st.pos().clone(), e5(st))
+}
+
+/// rule `tmpl_sep`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_sep(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        loop {
+            match specialized::lit(st.clone(), " ") {
+                Err(e) => return specialized::repeat_end(st, nodes, e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                }
+            }
+        }
+    }
+
+    fn e3(st: State) -> specialized::Result {
+        let mut nodes = vec![];
+        let (st, n) = e1(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, ",")?;
+        nodes.extend(n);
+        let (st, n) = e2(st)?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "\"")?;
+        nodes.extend(n);
+        let (st, n) = specialized::named("s", st.pos().clone(), r_tmpl_lit(st))?;
+        nodes.extend(n);
+        let (st, n) = specialized::lit(st, "\"")?;
+        nodes.extend(n);
+        Ok((st, nodes))
+    }
+
+    specialized::rule("tmpl_sep", None, st.pos().clone(), specialized::transf2(st.pos().clone(), e3(st), || t2rules!(t2_byname!("s"), )))
+}
+
+/// rule `tmpl_idx`
+#[allow(non_snake_case, clippy::manual_is_ascii_check)]
+pub fn r_tmpl_idx(st: State) -> specialized::Result {
+    fn e1(st: State) -> specialized::Result {
+        let mut st = st;
+        let mut nodes = vec![];
+        let mut count = 0;
+        loop {
+            match specialized::char_match(st.clone(), |ch| matches!(ch, '0'..='9'), "match  [('0', '9')]") {
+                Err(e) if count >= 1 => return specialized::repeat_end(st, nodes, e),
+                Err(e) => return Err(e),
+                Ok((next, n)) => {
+                    nodes.extend(n);
+                    if specialized::same_pos(&st, &next) {
+                        return Ok((next, nodes));
+                    }
+                    st = next;
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    fn e2(st: State) -> specialized::Result {
+        let err = None;
+        let err = match specialized::lit(st.clone(), "first") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match specialized::lit(st.clone(), "last") {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        let err = match e1(st.clone()) {
+            Ok(r) => return Ok(r),
+            Err(e) => specialized::or_error(err, e)?,
+        };
+        Err(specialized::or_end(&st, err))
+    }
+
+    specialized::rule("tmpl_idx", None, st.pos().clone(), e2(st))
+}
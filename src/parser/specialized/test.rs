@@ -0,0 +1,150 @@
+//-----------------------------------------------------------------------
+//
+//  mod specialized  TEST
+//
+//-----------------------------------------------------------------------
+
+use super::peg_parser;
+use crate::Peg;
+
+const CORPUS: &[&str] = &[
+    include_str!("../../rules_for_peg.peg"),
+    "
+    main    =   char+
+    char    =   'a'     -> A
+            /   'b'     -> B
+            /   .
+    ",
+    r#"
+    main    =   n:num (_ ',' _ n:num)*      -> $(n*, "+") $(#n) $(?n == "1": one | other)
+    num     =   [0-9]+ ('.' [0-9]+)?
+    _       =   (' ' / '\t')*
+            .desc  spaces  desc.
+    "#,
+    "
+    main    =   !'a' &'b' . eof?    -> $(:upper .1)$(:endl)
+    ",
+    "main = 'a'",
+    "
+    main    =   'a' /
+    ",
+    "
+    main    =   ('a'
+    ",
+    "
+    main    =   'a' -> $(
+    ",
+    "
+    main    =   error(\"critical\")
+    ",
+    "",
+    "  \n",
+    //  escapes on the peg are kept, `\n` is a backslash and an `n`
+    r#"main = '\d' "\n" [\t]     -> x\ny\n"#,
+    r#"main = 'a'\nb = "\\"\n"#,
+];
+
+#[test]
+fn test_specialized_peg_parser() {
+    let rules = Peg::new(include_str!("../../rules_for_peg.peg"))
+        .gen_rules()
+        .unwrap();
+
+    assert_eq!(rules.compare_specialized(peg_parser::parse, CORPUS), vec![]);
+    assert!(peg_parser::parse(CORPUS[11]).is_ok());
+    assert!(peg_parser::parse(CORPUS[12]).is_ok());
+    assert!(peg_parser::parse(CORPUS[5]).is_err());
+}
+
+#[test]
+fn test_specialized_parse_from() {
+    let rules = Peg::new(include_str!("../../rules_for_peg.peg"))
+        .gen_rules()
+        .unwrap();
+    let corpus = &["'a'", r#""\n""#, "[a-z]", "a", ""];
+
+    for rule in &["literal", "atom", "missing"] {
+        let specialized = |text: &str| peg_parser::parse_from(rule, text);
+        assert_eq!(
+            rules.compare_specialized_from(rule, specialized, corpus),
+            vec![]
+        );
+    }
+    assert!(peg_parser::parse_from("literal", "'a'").is_ok());
+    assert!(peg_parser::parse_from("missing", "'a'").is_err());
+}
+
+#[test]
+fn test_specialized_mismatch() {
+    let rules = Peg::new(
+        "
+        main    =   a  b
+        a       =   'a'
+        b       =   'b'
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+    let other = Peg::new(
+        "
+        main    =   a  bb
+        a       =   'a'
+        bb      =   'b' / 'c'
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+    let specialized = |text: &str| match other.parse(text) {
+        Err(crate::Error::ParserErr(e)) => Err(e),
+        result => Ok(result.unwrap()),
+    };
+
+    let mismatches = rules.compare_specialized(specialized, &["ab", "ac", "x"]);
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].text, "ab");
+    assert_eq!(mismatches[0].path, vec!["main", "b"]);
+    assert_eq!(mismatches[0].span.source("ab"), "b");
+    assert_eq!(mismatches[1].text, "ac");
+    assert_eq!(mismatches[1].path, Vec::<String>::new());
+    assert_eq!(mismatches[1].span.start().col(), 1);
+}
+
+#[test]
+fn test_specialized_code() {
+    let code = Peg::new(
+        r#"
+        main    =   id:[_a-z]+ &'='     -> $(id)
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .specialized_parser()
+    .unwrap();
+
+    assert!(code.contains("pub fn parse(text: &str)"));
+    assert!(code.contains("pub fn r_main(st: State)"));
+    assert!(code.contains("|ch| matches!(ch, '_' | 'a'..='z')"));
+    assert!(code.contains(r#"specialized::peek(st.clone(), specialized::lit(st, "="))"#));
+}
+
+#[test]
+fn test_specialized_left_recursion() {
+    let rules = Peg::new(
+        "
+        main    =   main '+' n
+                /   n
+        n       =   [0-9]
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    match rules.specialized_parser() {
+        Err(e @ crate::Error::GrammarErr(_)) => assert_eq!(
+            e.to_string(),
+            "errors on grammar:
+  rule main: left recursion not supported by specialized parser main -> main"
+        ),
+        _ => panic!("expected left recursion error"),
+    }
+}
@@ -4,14 +4,15 @@
 //
 //-----------------------------------------------------------------------
 
-use crate::parser::{expression::parse, Status};
+use crate::ast::Node;
+use crate::parser::{expression::parse, ErrPriority, Status};
 
 #[test]
 fn test_parse_expr_lit() {
     let rules = rules! {"main" => RuleInfo{expr: lit!("aaa"), descr: None} };
     let status_init = Status::init("aaaaaaaaaaaaaaaa", &rules);
 
-    let (status, _) = parse(status_init).ok().unwrap();
+    let (status, _) = parse(status_init, "main").ok().unwrap();
     assert!(status.pos.col == 3);
     assert!(status.pos.n == 3);
     assert!(status.pos.row == 0);
@@ -22,7 +23,7 @@ fn test_parse_expr_and_ok() {
     let rules = rules! {"main" => RuleInfo{expr: and![lit!("aa"), and![lit!("bb"), lit!("cc")]], descr: None} };
     let status_init = Status::init("aabbcc", &rules);
 
-    let (status, _) = parse(status_init).ok().unwrap();
+    let (status, _) = parse(status_init, "main").ok().unwrap();
     assert_eq!(status.pos.col, 6);
     assert_eq!(status.pos.n, 6);
     assert_eq!(status.pos.row, 0);
@@ -33,7 +34,7 @@ fn test_parse_expr_or_ok() {
     let rules = rules! {"main" => RuleInfo{expr: or![lit!("bb"), and![lit!("aa"), lit!("bb")]], descr: None} };
     let status_init = Status::init("aabb", &rules);
 
-    let (status, _) = parse(status_init).ok().unwrap();
+    let (status, _) = parse(status_init, "main").ok().unwrap();
     assert_eq!(status.pos.col, 4);
     assert_eq!(status.pos.n, 4);
     assert_eq!(status.pos.row, 0);
@@ -44,7 +45,7 @@ fn test_parse_expr_not_ok() {
     let rules = rules! {"main" => RuleInfo{expr: not!(lit!("bb")), descr: None} };
     let status_init = Status::init("aa", &rules);
 
-    let (status, _) = parse(status_init).ok().unwrap();
+    let (status, _) = parse(status_init, "main").ok().unwrap();
     assert_eq!(status.pos.col, 0);
     assert_eq!(status.pos.n, 0);
     assert_eq!(status.pos.row, 0);
@@ -56,7 +57,7 @@ fn test_parse_expr_repeat_ok() {
     {
         let status_init = Status::init("aaaaaa", &rules);
 
-        let (status, _) = parse(status_init).ok().unwrap();
+        let (status, _) = parse(status_init, "main").ok().unwrap();
         assert_eq!(status.pos.col, 6);
         assert_eq!(status.pos.n, 6);
         assert_eq!(status.pos.row, 0);
@@ -65,9 +66,173 @@ fn test_parse_expr_repeat_ok() {
     // {
     //     let status_init = Status::init("aaaaaa", rep![lit!("aa"), 0, 3]);
 
-    //     let result = parse(status_init).ok().unwrap();
+    //     let result = parse(status_init, "main").ok().unwrap();
     //     assert_eq!(result.status.pos.col, 6);
     //     assert_eq!(result.status.pos.n, 6);
     //     assert_eq!(result.status.pos.row, 0);
     // }
 }
+
+fn nested_par_rules() -> crate::parser::expression::SetOfRules {
+    rules! {
+        "main" => RuleInfo{expr: ref_rule!("expr"), descr: None},
+        "expr" => RuleInfo{expr: or![
+                and![ref_rule!("par"), lit!("+"), ref_rule!("expr")],
+                and![ref_rule!("par"), lit!("-"), ref_rule!("expr")],
+                ref_rule!("par")
+            ], descr: None},
+        "par" => RuleInfo{expr: or![
+                and![lit!("("), ref_rule!("expr"), lit!(")")],
+                lit!("a")
+            ], descr: None}
+    }
+}
+
+fn nested_par(deep: usize) -> String {
+    format!("{}a{}", "(".repeat(deep), ")".repeat(deep))
+}
+
+#[test]
+fn test_parse_memo_same_result() {
+    let rules = nested_par_rules();
+    let text = nested_par(4) + "+a-" + &nested_par(3);
+
+    let (status, ast) = parse(Status::init(&text, &rules), "main").ok().unwrap();
+    let (status_memo, ast_memo) = parse(Status::init(&text, &rules).with_memo(), "main")
+        .ok()
+        .unwrap();
+
+    assert_eq!(ast, ast_memo);
+    assert_eq!(status.pos, status_memo.pos);
+    assert_eq!(status_memo.pos.n, text.len());
+}
+
+#[test]
+fn test_parse_memo_deep_nesting() {
+    let rules = nested_par_rules();
+    //  exponential without memo
+    let text = nested_par(20);
+
+    let (status, _) = parse(Status::init(&text, &rules).with_memo(), "main")
+        .ok()
+        .unwrap();
+    assert_eq!(status.pos.n, text.len());
+}
+
+#[test]
+fn test_parse_memo_partial() {
+    let rules = nested_par_rules();
+    let text = nested_par(3) + "+";
+
+    let (status, _) = parse(Status::init(&text, &rules), "main").ok().unwrap();
+    let (status_memo, _) = parse(Status::init(&text, &rules).with_memo(), "main")
+        .ok()
+        .unwrap();
+    assert_eq!(status.pos, status_memo.pos);
+    assert_eq!(status_memo.pos.n, text.len() - 1);
+}
+
+#[test]
+fn test_parse_left_recursion_direct() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: ref_rule!("expr"), descr: None},
+        "expr" => RuleInfo{expr: or![
+                and![ref_rule!("expr"), lit!("-"), ref_rule!("num")],
+                ref_rule!("num")
+            ], descr: None},
+        "num" => RuleInfo{expr: ematch!(chlist "", from '0', to '9'), descr: None}
+    };
+
+    for status_init in [
+        Status::init("1-2-3", &rules),
+        Status::init("1-2-3", &rules).with_memo(),
+    ] {
+        let (status, ast) = parse(status_init, "main").ok().unwrap();
+        assert_eq!(status.pos.n, 5);
+
+        //  left associative  ((1-2)-3)
+        let expr = match ast {
+            Node::Rule((_, mut nodes, _)) => nodes.remove(0),
+            _ => panic!("expected main rule"),
+        };
+        match expr {
+            Node::Rule((name, nodes, _)) => {
+                assert_eq!(name, "expr");
+                assert_eq!(nodes.len(), 3);
+                match &nodes[0] {
+                    Node::Rule((name, nodes, _)) => {
+                        assert_eq!(name, "expr");
+                        assert_eq!(nodes.len(), 3);
+                    }
+                    _ => panic!("expected expr rule"),
+                }
+            }
+            _ => panic!("expected expr rule"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_left_recursion_indirect() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: ref_rule!("a"), descr: None},
+        "a" => RuleInfo{expr: and![ref_rule!("b"), lit!("x")], descr: None},
+        "b" => RuleInfo{expr: and![rep![ref_rule!("a"), 0, 1], lit!("y")], descr: None}
+    };
+
+    let (status, _) = parse(Status::init("yxyxyx", &rules), "main").ok().unwrap();
+    assert_eq!(status.pos.n, 6);
+
+    let (status, _) = parse(Status::init("yxyxyx", &rules).with_memo(), "main")
+        .ok()
+        .unwrap();
+    assert_eq!(status.pos.n, 6);
+}
+
+#[test]
+fn test_parse_left_recursion_no_seed() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: and![ref_rule!("main"), lit!("a")], descr: None}
+    };
+
+    let err = parse(Status::init("aaa", &rules), "main").err().unwrap();
+    assert_eq!(err.priority, ErrPriority::Critical);
+    assert_eq!(err.alternatives.expected, vec!["left recursion on main"]);
+}
+
+#[test]
+fn test_parse_left_recursion_error_expected() {
+    let rules = rules! {
+        "main" => RuleInfo{expr: and![ref_rule!("expr"), eof!()], descr: None},
+        "expr" => RuleInfo{expr: or![
+                and![ref_rule!("expr"), lit!("-"), ref_rule!("num")],
+                ref_rule!("num")
+            ], descr: None},
+        "num" => RuleInfo{expr: lit!("1"), descr: None}
+    };
+
+    let err = parse(Status::init("x", &rules), "main").err().unwrap();
+    assert_eq!(err.priority, ErrPriority::Normal);
+    assert_eq!(err.alternatives.expected, vec!["1"]);
+}
+
+#[test]
+fn test_parse_memo_debug_error_path() {
+    //  `x` fails on 0 inside the `not` and it's reused from `b`
+    let rules = rules! {
+        "main" => RuleInfo{expr: and![not!(ref_rule!("x")), ref_rule!("b")], descr: None},
+        "b" => RuleInfo{expr: ref_rule!("x"), descr: None},
+        "x" => RuleInfo{expr: lit!("x"), descr: None}
+    };
+    let parse_debug = |status: Status| match parse(status, "main") {
+        Err(e) => e.parsing_rules,
+        Ok(_) => panic!("expected error"),
+    };
+
+    let expected = vec!["r:main", "r:b", "r:x"];
+    assert_eq!(parse_debug(Status::init_debug("z", &rules, true)), expected);
+    assert_eq!(
+        parse_debug(Status::init_debug("z", &rules, true).with_memo()),
+        expected
+    );
+}
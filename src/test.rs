@@ -0,0 +1,1026 @@
+//-----------------------------------------------------------------------
+//
+//  fluent API  TEST
+//
+//-----------------------------------------------------------------------
+
+use crate::Peg;
+
+#[test]
+fn test_left_recursion_left_associative() {
+    let rules = Peg::new(
+        "
+        main    =   expr
+
+        expr    =   expr  '-'  num      -> ($(expr)-$(num))
+                /   num
+
+        num     =   [0-9]+
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    let result = rules
+        .parse("1-2-3")
+        .ok()
+        .unwrap()
+        .replace(None)
+        .ok()
+        .unwrap();
+    assert_eq!(result.str(), "((1-2)-3)");
+}
+
+#[test]
+fn test_parse_utf8_full_input() {
+    let rules = Peg::new(
+        "
+        main    =   word (' ' word)*
+        word    =   (!' ' .)+
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    assert!(rules.parse("el niño comió 漢字").is_ok());
+    assert!(rules.parse_memo("el niño comió 漢字").is_ok());
+}
+
+#[test]
+fn test_parse_utf8_error() {
+    let rules = Peg::new(
+        "
+        main    =   'año'  ' '+  'ñu'
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    match rules.parse("año  ñú") {
+        Err(crate::Error::ParserErr(e)) => {
+            assert_eq!(e.pos.row, 0);
+            assert_eq!(e.pos.col, 6);
+            assert_eq!(e.pos.n, "año  ñ".len());
+        }
+        _ => panic!("expected parser error"),
+    }
+}
+
+fn find_rule<'a>(node: &'a crate::Node, rule: &str) -> Option<&'a crate::Node> {
+    use crate::Node;
+
+    let nodes = match node {
+        Node::Rule((name, _, _)) if name == rule => return Some(node),
+        Node::Rule((_, nodes, _)) | Node::Named((_, nodes, _)) => nodes,
+        Node::Transf2(t2) => &t2.nodes,
+        Node::Val(_) | Node::Eof(_) => return None,
+    };
+    nodes.iter().find_map(|n| find_rule(n, rule))
+}
+
+#[test]
+fn test_node_span() {
+    let rules = Peg::new(
+        "
+        main    =   _  'let'  _  id  _  '='  _  num
+        id      =   [ña-z]+
+        num     =   [0-9]+
+        _       =   (!'=' ![ña-z0-9] .)*
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    let text = "let\n  año = 42";
+    let ast = rules.parse(text).ok().unwrap();
+    assert_eq!(ast.source(text), text);
+
+    let id = find_rule(&ast, "id").unwrap();
+    assert_eq!(id.source(text), "año");
+    assert_eq!(id.span().start().row(), 1);
+    assert_eq!(id.span().start().col(), 2);
+    assert_eq!(id.span().end().col(), 5);
+    assert_eq!(id.span().end().n(), "let\n  año".len());
+
+    let num = find_rule(&ast, "num").unwrap();
+    assert_eq!(num.source(text), "42");
+    assert_eq!(num.span().start().col(), 8);
+}
+
+#[test]
+fn test_parser_error_display() {
+    let rules = Peg::new(
+        "
+        main    =   'let' _ id
+        id      =   [a-z]+
+                /   expected(\"identifier\")
+                .desc  Variable name  desc.
+        _       =   ' '*
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    let err = match rules.parse("let  9") {
+        Err(crate::Error::ParserErr(e)) => e,
+        _ => panic!("expected parser error"),
+    };
+    assert_eq!(err.expected(), vec!["identifier"]);
+    assert_eq!(err.context(), Some("Variable name"));
+    assert_eq!(err.line_before(), "let  ");
+    assert_eq!(err.line_after(), "9");
+    assert_eq!(err.priority(), crate::ErrPriority::Critical);
+    assert_eq!(
+        err.to_string(),
+        "error: expected \"identifier\"
+ --> 1:6
+  |
+1 | let  9
+  |      ^
+  = rule: Variable name"
+    );
+}
+
+#[test]
+fn test_parser_error_display_dedup() {
+    let rules = Peg::new(
+        "
+        main    =   ('a' / 'b' / 'a')  'c'
+        ",
+    )
+    .gen_rules()
+    .ok()
+    .unwrap();
+
+    let err = rules.parse("x").err().unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("error: expected \"a\", \"b\"\n --> 1:1"));
+}
+
+#[test]
+fn test_parser_error_display_unexpected() {
+    use crate::parser::{ErrorAlternatives, Status};
+
+    //  nothing expected (as when cutting a left recursion)
+    let rules = crate::parser::expression::SetOfRules::empty();
+    let err = crate::ParserError::from_status(
+        &Status::init("let a", &rules),
+        &ErrorAlternatives::empty(),
+        crate::ErrPriority::Normal,
+    );
+    assert_eq!(
+        err.to_string(),
+        "error: unexpected input
+ --> 1:1
+  |
+1 | let a
+  | ^"
+    );
+}
+
+#[test]
+fn test_gen_rules_ir_error_possition() {
+    //  an end of line inside a literal breaks the IR
+    let result = Peg::new(
+        "
+        main    =   a  b
+        a       =   'a'
+        b       =   'b
+'
+        ",
+    )
+    .gen_rules();
+
+    match result {
+        Err(crate::Error::IrErr(e)) => {
+            assert_eq!(e.rule(), Some("b"));
+            assert_eq!(e.pos().unwrap().row(), 3);
+            assert_eq!(e.pos().unwrap().col(), 8);
+            assert!(e.to_string().contains("4 |         b       =   'b"));
+        }
+        _ => panic!("expected IR error"),
+    }
+}
+
+#[test]
+fn test_gen_rules_peg_error() {
+    let result = Peg::new("main = ('a'").gen_rules();
+
+    match result {
+        Err(crate::Error::ParserErr(_)) => (),
+        _ => panic!("expected parser error"),
+    }
+}
+
+#[test]
+fn test_gen_rules_grammar_errors() {
+    let result = Peg::new(
+        "
+        main    =   a  b
+        a       =   'a'  c
+        a       =   'A'
+        ",
+    )
+    .gen_rules();
+
+    match result {
+        Err(e @ crate::Error::GrammarErr(_)) => assert_eq!(
+            e.to_string(),
+            "errors on grammar:
+  rule a: defined more than once
+  rule a: undefined rule c
+  rule main: undefined rule b"
+        ),
+        _ => panic!("expected grammar error"),
+    }
+}
+
+#[test]
+fn test_gen_rules_for_peg_grammar() {
+    if let Err(e) = Peg::new(include_str!("rules_for_peg.peg")).gen_rules() {
+        panic!("{}", e);
+    }
+}
+
+#[test]
+fn test_gen_rules_nullable_repeat() {
+    let result = Peg::new(
+        "
+        main    =   (a / b)*
+        a       =   'a'
+        b       =   'b'?
+        ",
+    )
+    .gen_rules();
+
+    match result {
+        Err(e @ crate::Error::GrammarErr(_)) => assert_eq!(
+            e.to_string(),
+            "errors on grammar:
+  rule main: repeating an expression that can match empty (infinite loop)"
+        ),
+        _ => panic!("expected grammar error"),
+    }
+}
+
+#[test]
+fn test_gen_rules_left_recursion_without_seed() {
+    let result = Peg::new(
+        "
+        main    =   a  'x'
+        a       =   main  'y'
+        ",
+    )
+    .gen_rules();
+
+    match result {
+        Err(e @ crate::Error::GrammarErr(_)) => assert_eq!(
+            e.to_string(),
+            "errors on grammar:
+  rule a: left recursion without alternative to start a -> main -> a"
+        ),
+        _ => panic!("expected grammar error"),
+    }
+}
+
+#[test]
+fn test_lint_display() {
+    let rules = Peg::new(
+        "
+        main    =   'a' / 'ab'
+        other   =   'o'
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    assert_eq!(
+        rules
+            .lint()
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "rule other: not reachable from start rule",
+            "rule main: alternative 2 never matches, a previous one matches first"
+        ]
+    );
+}
+
+#[test]
+fn test_lint_from() {
+    let rules = Peg::new(
+        "
+        main    =   'm'
+        expr    =   atom+
+        atom    =   'a'
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    let unused = |issues: Vec<crate::Issue>| {
+        issues
+            .into_iter()
+            .filter_map(|i| i.rule)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(unused(rules.lint()), vec!["atom", "expr"]);
+    assert_eq!(unused(rules.lint_from("expr")), vec!["main"]);
+}
+
+#[test]
+fn test_replace_closure_with_args() {
+    let mut counter = 0;
+    let mut functions = |name: &str, args: &[&str], ctx: &crate::FnContext| match name {
+        "count" => {
+            counter += 1;
+            Ok(Some(counter.to_string()))
+        }
+        "pad" => {
+            let width = args[0].parse::<usize>().map_err(|e| e.to_string())?;
+            let txt = ctx.by_name(args[1]).unwrap_or("");
+            Ok(Some(format!("{:>width$}", txt, width = width)))
+        }
+        _ => Ok(None),
+    };
+
+    let result = Peg::new(
+        "
+        main    =   line+
+        line    =   num:num ' '?        -> $(:count):$(:pad 4 num)$(:endl)
+        num     =   [0-9]+
+        ",
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("1 22")
+    .unwrap()
+    .replace(Some(&mut functions))
+    .unwrap();
+
+    assert_eq!(result.str(), "1:   1\n2:  22\n");
+}
+
+#[test]
+fn test_replace_closure_error() {
+    let mut functions =
+        |name: &str, _: &[&str], _: &crate::FnContext| Err(format!("{} not allowed", name));
+
+    let result = Peg::new(
+        "
+        main    =   'a'     -> $(:forbidden x)
+        ",
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("a")
+    .unwrap()
+    .replace(Some(&mut functions));
+
+    match result {
+        Err(crate::Error::ReplaceErr(e)) => assert_eq!(
+            e,
+            "error calling function forbidden x: forbidden not allowed
+ --> 1:1
+ = rule: main
+ = template: $(:forbidden x)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+}
+
+#[test]
+fn test_replace_by_pos() {
+    let rules = Peg::new(
+        "
+        main    =   num  (op  num)?     -> $(.3)$(.2)$(.1)
+        op      =   '+' / '-'
+        num     =   [0-9]+
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    let result = rules.parse("1-2").unwrap().replace(None).unwrap();
+    assert_eq!(result.str(), "2-1");
+
+    match rules.parse("1").unwrap().replace(None) {
+        Err(crate::Error::ReplaceErr(e)) => assert_eq!(
+            e,
+            "position 3 out of range on replace template, there are 1 elements (1 based)
+ --> 1:1
+ = rule: main
+ = template: $(.3)$(.2)$(.1)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+
+    assert!(crate::gcode::rust_from_rules(&rules).contains("t2_bypos!(3)"));
+}
+
+#[test]
+fn test_replace_by_pos_generated_code() {
+    let rules = rules! {
+        "main" => RuleInfo{ expr:transf2!( and!(ref_rule!("a"), lit!("-"), ref_rule!("b")),
+                                           t2rules!(t2_bypos!(2), t2_text!("-"), t2_bypos!(1)) ),
+                            descr:None },
+        "a" => RuleInfo{ expr:lit!("a"), descr:None },
+        "b" => RuleInfo{ expr:lit!("b"), descr:None }
+    };
+
+    let result = rules.parse("a-b").unwrap().replace(None).unwrap();
+    assert_eq!(result.str(), "b-a");
+}
+
+#[test]
+fn test_replace_repeated_names() {
+    let rules = Peg::new(
+        r#"
+        main    =   '['  (item:num  ','?)*  ']'
+                        -> $(#item): [$(item*, ", ")] $(item[first]) $(item[2]) $(item[last]) $(item)
+
+        num     =   [0-9]+
+        "#,
+    )
+    .gen_rules()
+    .unwrap();
+
+    let result = rules.parse("[1,22,333]").unwrap().replace(None).unwrap();
+    assert_eq!(result.str(), "3: [1, 22, 333] 1 22 333 333");
+
+    match rules.parse("[1]").unwrap().replace(None) {
+        Err(crate::Error::ReplaceErr(e)) => assert_eq!(
+            e,
+            "index 2 out of range for name item on replace template, \
+             there are 1 occurrences (1 based)
+ --> 1:1
+ = rule: main
+ = template: $(#item): [$(item*, \", \")] $(item[first]) $(item[2]) $(item[last]) $(item)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+
+    let code = crate::gcode::rust_from_rules(&rules);
+    assert!(code.contains(r#"t2_byname_join!("item", ", ")"#));
+    assert!(code.contains(r#"t2_byname_count!("item")"#));
+    assert!(code.contains(r#"t2_byname_index!("item", NameIndex::Nth(2))"#));
+}
+
+#[test]
+fn test_replace_repeated_names_escaped_separator() {
+    let result = Peg::new(
+        r#"
+        main    =   (l:[a-z])+      -> $(l*, "\n")$(l*)
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("abc")
+    .unwrap()
+    .replace(None)
+    .unwrap();
+
+    assert_eq!(result.str(), "a\nb\ncabc");
+}
+
+#[test]
+fn test_replace_conditional() {
+    let rules = Peg::new(
+        r#"
+        main    =   sign?  num:[0-9]      -> $(?sign: NEG $(num)| POS $(num))$(?sign == "+": (explicit$(:rpar)) $(?num == "0":zero)
+
+        sign    =   '-' / '+'
+        "#,
+    )
+    .gen_rules()
+    .unwrap();
+
+    let replace = |input| rules.parse(input).unwrap().replace(None).unwrap().str();
+    assert_eq!(replace("-1"), "NEG 1 ");
+    assert_eq!(replace("+1"), "NEG 1(explicit) ");
+    assert_eq!(replace("0"), "POS 0 zero");
+
+    assert!(crate::gcode::rust_from_rules(&rules)
+        .contains(r#"t2_if_eq!("num", "0", t2rules!(t2_text!("zero"), ), t2rules!()), "#));
+}
+
+#[test]
+fn test_replace_strict() {
+    let rules = rules! {
+        "main" => RuleInfo{ expr:and!(lit!("a"), ref_rule!("b")), descr:None },
+        "b" => RuleInfo{ expr:transf2!( and!(lit!("b"), named!("c", lit!("c"))),
+                                        t2rules!(t2_byname!("c"), t2_byname!("x"), t2_funct!("unknown")) ),
+                         descr:None }
+    };
+    let ast = rules.parse("abc").unwrap();
+
+    assert_eq!(
+        ast.replace(None).unwrap().str(),
+        "acname<x/missing>?unknown_fn?<unknown>"
+    );
+
+    match ast.replace_strict(None) {
+        Err(e @ crate::Error::ReplaceErr(_)) => assert_eq!(
+            e.to_string(),
+            "error on replace: missing name x
+ --> 1:2
+ = rule: b
+ = template: $(c)$(x)$(:unknown)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+
+    let mut functions =
+        |_: &str, _: &[&str], ctx: &crate::FnContext| Ok(ctx.by_name("c").map(|_| "X".to_string()));
+    match ast.replace_strict(Some(&mut functions)) {
+        Err(e @ crate::Error::ReplaceErr(_)) => assert_eq!(
+            e.to_string(),
+            "error on replace: missing name x
+ --> 1:2
+ = rule: b
+ = template: $(c)$(x)$(:unknown)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+}
+
+#[test]
+fn test_replace_strict_unknown_function() {
+    let result = Peg::new(
+        "
+        main    =   a:'a'   -> $(a)$(:unknown 1)
+        ",
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("a")
+    .unwrap()
+    .replace_strict(None);
+
+    match result {
+        Err(e @ crate::Error::ReplaceErr(_)) => assert_eq!(
+            e.to_string(),
+            "error on replace: unknown function unknown 1
+ --> 1:1
+ = rule: main
+ = template: $(a)$(:unknown 1)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+}
+
+#[test]
+fn test_gen_rules_undefined_template_names() {
+    let result = Peg::new(
+        "
+        main    =   a:'a'  b:'b'  c     -> $(a)$(b)$(x)$(c)
+        c       =   'c'                 -> $(?c: $(#d))
+        ",
+    )
+    .gen_rules();
+
+    match result {
+        Err(e @ crate::Error::GrammarErr(_)) => assert_eq!(
+            e.to_string(),
+            "errors on grammar:
+  rule c: name c used on template is not on its expression
+  rule c: name d used on template is not on its expression
+  rule main: name x used on template is not on its expression"
+        ),
+        _ => panic!("expected grammar error"),
+    }
+}
+
+#[test]
+fn test_replace_to_writer() {
+    let ast = Peg::new(
+        r#"
+        main    =   line*
+        line    =   (item:[a-z]  ','?)+  '.'    -> [$(item*, " ")]$(:endl)
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("a,b.c.")
+    .unwrap();
+
+    let mut out = Vec::<u8>::new();
+    ast.replace_to(&mut out, None).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "[a b]\n[c]\n");
+    assert_eq!(ast.replace(None).unwrap().str(), "[a b]\n[c]\n");
+
+    struct Failing;
+    impl std::io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    match ast.replace_to(&mut Failing, None) {
+        Err(e @ crate::Error::ReplaceErr(_)) => {
+            assert_eq!(e.to_string(), "error on replace: writing output: disk full")
+        }
+        _ => panic!("expected replace error"),
+    }
+}
+
+#[test]
+fn test_replace_to_streams_nested_nodes() {
+    let ast = Peg::new(
+        r#"
+        main    =   '('  m:main?  ')'       -> <$(m)>
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse(&format!("{}{}", "(".repeat(50), ")".repeat(50)))
+    .unwrap();
+
+    #[derive(Default)]
+    struct Counting {
+        out: Vec<u8>,
+        writes: usize,
+        largest: usize,
+    }
+    impl std::io::Write for Counting {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.largest = self.largest.max(buf.len());
+            self.out.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    //  nodes are written as they are replaced, not copied on every level
+    let mut out = Counting::default();
+    ast.replace_to(&mut out, None).unwrap();
+    assert_eq!(
+        String::from_utf8(out.out).unwrap(),
+        format!("{}{}", "<".repeat(50), ">".repeat(50))
+    );
+    assert_eq!(out.writes, 100);
+    assert_eq!(out.largest, 1);
+}
+
+#[test]
+fn test_replace_indent() {
+    let result = Peg::new(
+        r#"
+        main    =   stmt+
+        stmt    =   block  /  (s:[a-z] ';'    -> $(s);$(:endl)
+                              )
+        block   =   '{'  stmt*  '}'         -> {$(:indent)$(:endl)$(stmt*)$(:dedent)}$(:endl)
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("a;{b;{c;}d;}e;")
+    .unwrap()
+    .replace(None)
+    .unwrap();
+
+    assert_eq!(
+        result.str(),
+        "a;
+{
+    b;
+    {
+        c;
+    }
+    d;
+}
+e;
+"
+    );
+}
+
+#[test]
+fn test_replace_indent_nested_not_closed() {
+    let result = Peg::new(
+        r#"
+        main    =   (s:[a-z]  -> $(s)$(:indent)$(:endl)
+                    )
+                    (t:[a-z]  -> $(t)$(:endl)
+                    )
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse("ab")
+    .unwrap()
+    .replace(None)
+    .unwrap();
+
+    assert_eq!(result.str(), "a\nb\n");
+}
+
+#[test]
+fn test_replace_indent_arguments_and_callback() {
+    let rules = Peg::new(
+        r#"
+        main    =   (s:[a-z]  -> $(:indent)$(:endl)$(s)$(:dedent)
+                    )
+        "#,
+    )
+    .gen_rules()
+    .unwrap();
+    let ast = rules.parse("a").unwrap();
+
+    //  a callback doesn't redefine them
+    let mut functions =
+        |name: &str, _: &[&str], _: &crate::FnContext| Ok(Some(format!("<{}>", name)));
+    assert_eq!(
+        ast.replace(Some(&mut functions)).unwrap().str(),
+        "    <endl>a"
+    );
+
+    let ast = Peg::new("main = 'a'  -> $(:indent 2)a\n")
+        .gen_rules()
+        .unwrap()
+        .parse("a")
+        .unwrap();
+    match ast.replace(None) {
+        Err(e @ crate::Error::ReplaceErr(_)) => assert!(e
+            .to_string()
+            .starts_with("error on replace: function indent doesn't receive arguments")),
+        _ => panic!("expected replace error"),
+    }
+}
+
+#[test]
+fn test_replace_predefined_functions() {
+    let result = Peg::new(
+        r#"
+        main    =   item+
+        item    =   id  ':'  esc  ';'     -> $(id)$(esc)
+        id      =   id:([_a-zA-Z]+)
+                    -> $(:counter)/$(:counter ids)/$(:counter): $(:upper id) $(:lower id) $(:camel id) $(:snake id) $(:len id)$(:endl)
+        esc     =   t:(( !';' . )*)
+                    -> [$(:trim t)]$(:endl)"$(:esc_rust t)" "$(:esc_json t)" $(:esc_xml t) $(:esc_shell t)$(:endl)
+        "#,
+    )
+    .gen_rules()
+    .unwrap()
+    .parse(r#"HTTPServer_name: a "b" <c> & 'd' \ ;helloWorld:x;"#)
+    .unwrap()
+    .replace(None)
+    .unwrap()
+    .str();
+
+    assert_eq!(
+        result,
+        r#"1/1/2: HTTPSERVER_NAME httpserver_name httpServerName http_server_name 15
+[a "b" <c> & 'd' \]
+" a \"b\" <c> & \'d\' \\ " " a \"b\" <c> & 'd' \\ "  a &quot;b&quot; &lt;c&gt; &amp; &apos;d&apos; \  ' a "b" <c> & '\''d'\'' \ '
+3/2/4: HELLOWORLD helloworld helloWorld hello_world 10
+[x]
+"x" "x" x 'x'
+"#
+    );
+}
+
+#[test]
+fn test_replace_predefined_functions_errors() {
+    let rules = Peg::new(
+        "
+        main    =   a:'a'  -> $(:upper)$(:upper a)
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+    let ast = rules.parse("a").unwrap();
+
+    match ast.replace(None) {
+        Err(e @ crate::Error::ReplaceErr(_)) => assert_eq!(
+            e.to_string(),
+            "error on replace: function upper requires a name as argument
+ --> 1:1
+ = rule: main
+ = template: $(:upper)$(:upper a)"
+        ),
+        _ => panic!("expected replace error"),
+    }
+
+    //  the name given to the function is checked with the grammar
+    assert!(matches!(
+        Peg::new("main = a:'a'  -> $(:upper b)\n").gen_rules(),
+        Err(crate::Error::GrammarErr(_))
+    ));
+}
+
+#[test]
+fn test_parse_from() {
+    let rules = Peg::new(
+        "
+        main    =   '[' list ']'
+        list    =   num (',' num)*
+        num     =   n:[0-9]+    -> <$(n)>
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    assert!(rules.parse("1,2").is_err());
+    assert_eq!(
+        rules
+            .parse_from("list", "1,2")
+            .unwrap()
+            .replace(None)
+            .unwrap()
+            .str(),
+        "<1>,<2>"
+    );
+    assert_eq!(
+        rules
+            .parse_debug_from("num", "42")
+            .unwrap()
+            .replace(None)
+            .unwrap()
+            .str(),
+        "<42>"
+    );
+    assert!(rules.parse_from("num", "4,2").is_err());
+    assert_eq!(
+        rules.parse_memo_from("list", "1,2").unwrap(),
+        rules.parse_from("list", "1,2").unwrap()
+    );
+    assert!(rules
+        .parse_memo_debug_from("list", "1,x")
+        .unwrap_err()
+        .to_string()
+        .contains("= parsing: r:list"));
+}
+
+#[test]
+fn test_parse_from_missing_rule() {
+    let rules = Peg::new(
+        "
+        main    =   'a'
+        ",
+    )
+    .gen_rules()
+    .unwrap();
+
+    match rules.parse_from("other", "a") {
+        Err(e @ crate::Error::MissingStartRule(_)) => {
+            assert_eq!(e.to_string(), "missing start rule other")
+        }
+        _ => panic!("expected missing start rule error"),
+    }
+}
+
+#[test]
+fn test_parse_prefix() {
+    let rules = Peg::new(
+        r#"
+        main    =   stmt
+        stmt    =   id:[a-z]+ ';'     -> <$(id)>
+        "#,
+    )
+    .gen_rules()
+    .unwrap();
+
+    let text = "abc;de;\nf";
+    let (ast, end, rest) = rules.parse_prefix(text).unwrap();
+    assert_eq!(ast.replace(None).unwrap().str(), "<abc>");
+    assert_eq!((end.n(), end.row(), end.col()), (4, 0, 4));
+    assert_eq!(rest, "de;\nf");
+
+    let (ast, end, rest) = rules.parse_prefix_from("stmt", rest).unwrap();
+    assert_eq!(ast.replace(None).unwrap().str(), "<de>");
+    assert_eq!(end.n(), 3);
+    assert_eq!(rest, "\nf");
+
+    assert!(rules.parse_prefix(rest).is_err());
+    assert!(rules.parse(text).is_err());
+}
+
+#[test]
+fn test_build_peg_files() {
+    let dir = std::env::temp_dir().join(format!("yepp_build_{}", std::process::id()));
+    let out_dir = dir.join("out");
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(
+        dir.join("sub").join("calc.peg"),
+        r###"
+        main    =   "a#" '"##'
+        "###,
+    )
+    .unwrap();
+
+    let generated = crate::Build::new()
+        .dir(&dir)
+        .out_dir(&out_dir)
+        .cargo_metadata(false)
+        .compile()
+        .unwrap();
+    assert_eq!(generated, vec![out_dir.join("sub").join("calc.rs")]);
+    let code = std::fs::read_to_string(&generated[0]).unwrap();
+    assert!(code.contains("pub fn rules() -> yepp::SetOfRules"));
+    assert!(code.contains(r####"lit!("a#"), lit!("\"##")"####));
+
+    std::fs::write(dir.join("bad.peg"), "main = 'a' / ").unwrap();
+    match crate::Build::new()
+        .file(dir.join("bad.peg"))
+        .out_dir(&out_dir)
+        .cargo_metadata(false)
+        .compile()
+    {
+        Err(crate::BuildError::Grammar(path, _)) => assert_eq!(path, dir.join("bad.peg")),
+        _ => panic!("expected grammar error"),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_build_peg_files_duplicated_module() {
+    let dir = std::env::temp_dir().join(format!("yepp_build_dup_{}", std::process::id()));
+    let out_dir = dir.join("out");
+    std::fs::create_dir_all(dir.join("a")).unwrap();
+    std::fs::create_dir_all(dir.join("b")).unwrap();
+    std::fs::write(dir.join("a").join("calc.peg"), "main = 'a'").unwrap();
+    std::fs::write(dir.join("b").join("calc.peg"), "main = 'b'").unwrap();
+
+    let compile = |build: crate::Build| build.out_dir(&out_dir).cargo_metadata(false).compile();
+    match compile(
+        crate::Build::new()
+            .file(dir.join("a").join("calc.peg"))
+            .file(dir.join("b").join("calc.peg")),
+    ) {
+        Err(e @ crate::BuildError::DuplicatedModule(..)) => assert_eq!(
+            e.to_string(),
+            format!(
+                "{}: generated from {} and {}",
+                out_dir.join("calc.rs").display(),
+                dir.join("a").join("calc.peg").display(),
+                dir.join("b").join("calc.peg").display()
+            )
+        ),
+        _ => panic!("expected duplicated module"),
+    }
+    match compile(
+        crate::Build::new()
+            .file(dir.join("a").join("calc.peg"))
+            .dir(dir.join("a")),
+    ) {
+        Err(crate::BuildError::DuplicatedModule(module, ..)) => {
+            assert_eq!(module, out_dir.join("calc.rs"))
+        }
+        _ => panic!("expected duplicated module"),
+    }
+    assert!(!out_dir.exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_build_peg_files_escapes() {
+    let dir = std::env::temp_dir().join(format!("yepp_build_esc_{}", std::process::id()));
+    let peg = r#"
+        main    =   '\d'  "\n"  [\t]     -> "\n
+        "#;
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("esc.peg"), peg).unwrap();
+
+    let generated = crate::Build::new()
+        .file(dir.join("esc.peg"))
+        .out_dir(&dir)
+        .cargo_metadata(false)
+        .compile()
+        .unwrap();
+    let code = std::fs::read_to_string(&generated[0]).unwrap();
+    //  the same text the interpreted rules match
+    assert!(code.contains(r#"lit!("\\d"), lit!("\\n"), ematch!(chlist "\\t", from2 vec![])"#));
+    assert!(code.contains(r#"t2_text!("\"\\n")"#));
+
+    let rules = Peg::new(peg).gen_rules().unwrap();
+    let result = rules.parse(r"\d\n\").unwrap().replace(None).unwrap();
+    assert_eq!(result.str(), r#""\n"#);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_process_peg_files_io_error() {
+    let dir = std::env::temp_dir().join(format!("yepp_proc_missing_{}", std::process::id()));
+
+    match crate::process_peg_files(&dir) {
+        Err(e @ crate::Error::IoErr(..)) => {
+            assert!(e.to_string().starts_with(&format!("{}: ", dir.display())))
+        }
+        _ => panic!("expected io error"),
+    }
+}